@@ -0,0 +1,42 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use simple_encode::{Base16, Base32, Base36, Base58, Base62, Base64, Base85, Base91, Encoding};
+
+fn input(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 + i / 7) as u8).collect()
+}
+
+fn bench_codec(c: &mut Criterion, codec: &dyn Encoding, len: usize) {
+    let data = input(len);
+    let encoded = codec.encode(&data);
+
+    let mut group = c.benchmark_group(codec.name());
+    group.throughput(Throughput::Bytes(len as u64));
+    group.bench_with_input(BenchmarkId::new("encode", len), &data, |b, data| {
+        b.iter(|| codec.encode(black_box(data)))
+    });
+    group.bench_with_input(BenchmarkId::new("decode", len), &encoded, |b, encoded| {
+        b.iter(|| codec.decode(black_box(encoded)).unwrap())
+    });
+    group.finish();
+}
+
+fn block_codecs(c: &mut Criterion) {
+    // the block codecs are linear, so use a large input
+    let len = 64 * 1024;
+    bench_codec(c, &Base16 {}, len);
+    bench_codec(c, &Base32 {}, len);
+    bench_codec(c, &Base64 {}, len);
+    bench_codec(c, &Base85 {}, len);
+    bench_codec(c, &Base91 {}, len);
+}
+
+fn radix_codecs(c: &mut Criterion) {
+    // the radix codecs are quadratic in the input length
+    let len = 256;
+    bench_codec(c, &Base36 {}, len);
+    bench_codec(c, &Base58 {}, len);
+    bench_codec(c, &Base62 {}, len);
+}
+
+criterion_group!(benches, block_codecs, radix_codecs);
+criterion_main!(benches);
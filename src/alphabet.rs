@@ -0,0 +1,111 @@
+/// Marks bytes that are not part of an alphabet in the decode table.
+const INVALID: u8 = 0xff;
+
+/// Symbols of an `N` character alphabet with its encode and decode tables
+/// computed at compile time.
+///
+/// [`Alphabet::new`] is a `const fn` that panics unless the symbols are `N`
+/// unique ASCII characters, so a malformed alphabet stored in a `const` is a
+/// compile error rather than a runtime one:
+///
+/// ```
+/// use simple_encode::Alphabet;
+///
+/// const OCTAL: Alphabet<8> = Alphabet::new("01234567");
+/// assert_eq!(OCTAL.symbol(5), b'5');
+/// assert_eq!(OCTAL.value(b'7'), Some(7));
+/// assert_eq!(OCTAL.value(b'8'), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Alphabet<const N: usize> {
+    symbols: &'static str,
+    encode: [u8; N],
+    decode: [u8; 256],
+}
+
+impl<const N: usize> Alphabet<N> {
+    /// Builds the tables for `symbols`, whose position gives their value.
+    pub const fn new(symbols: &'static str) -> Alphabet<N> {
+        assert!(N <= 255, "an alphabet has at most 255 symbols");
+        let bytes = symbols.as_bytes();
+        assert!(bytes.len() == N, "alphabet has the wrong number of symbols");
+
+        let mut encode = [0u8; N];
+        let mut decode = [INVALID; 256];
+        let mut i = 0;
+        while i < N {
+            let byte = bytes[i];
+            assert!(byte.is_ascii(), "alphabet symbols must be ASCII");
+            assert!(
+                decode[byte as usize] == INVALID,
+                "alphabet symbols must be unique"
+            );
+            encode[i] = byte;
+            decode[byte as usize] = i as u8;
+            i += 1;
+        }
+
+        Alphabet {
+            symbols,
+            encode,
+            decode,
+        }
+    }
+
+    /// Also accepts the other case of every letter when decoding.
+    pub const fn ignore_case(mut self) -> Alphabet<N> {
+        let mut i = 0;
+        while i < N {
+            let byte = self.encode[i];
+            let other = if byte.is_ascii_lowercase() {
+                byte.to_ascii_uppercase()
+            } else {
+                byte.to_ascii_lowercase()
+            };
+            if other != byte {
+                assert!(
+                    self.decode[other as usize] == INVALID,
+                    "alphabet is not case insensitive"
+                );
+                self.decode[other as usize] = i as u8;
+            }
+            i += 1;
+        }
+        self
+    }
+
+    /// Also decodes `alias` as the value of `symbol`, for characters that
+    /// are easily mistaken for a symbol.
+    pub const fn with_alias(mut self, alias: u8, symbol: u8) -> Alphabet<N> {
+        assert!(
+            self.decode[alias as usize] == INVALID,
+            "alias is already a symbol"
+        );
+        assert!(
+            self.decode[symbol as usize] != INVALID,
+            "aliased symbol is not in the alphabet"
+        );
+        self.decode[alias as usize] = self.decode[symbol as usize];
+        self
+    }
+
+    /// The symbols ordered by value.
+    pub const fn as_str(&self) -> &'static str {
+        self.symbols
+    }
+
+    /// Symbol for `value`, which must be less than `N`.
+    #[inline]
+    pub fn symbol(&self, value: usize) -> u8 {
+        self.encode[value]
+    }
+
+    /// Value of the symbol `byte`, or `None` if it is not in the alphabet.
+    #[inline]
+    pub fn value(&self, byte: u8) -> Option<u8> {
+        match self.decode[byte as usize] {
+            INVALID => None,
+            value => Some(value),
+        }
+    }
+}
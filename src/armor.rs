@@ -0,0 +1,386 @@
+//! OpenPGP ASCII armor (RFC 4880 §6.2): Base64 wrapped at 76 columns
+//! between BEGIN and END lines, with `Key: Value` armor headers and a CRC-24
+//! checksum line.
+//!
+//! ```
+//! use simple_encode::armor::{self, Armor, ArmorKind};
+//!
+//! let armored = Armor::new(ArmorKind::Message, b"Hello, world".to_vec())
+//!     .with_header("Comment", "greeting")
+//!     .encode();
+//! assert_eq!(
+//!     armored,
+//!     "-----BEGIN PGP MESSAGE-----\n\
+//!      Comment: greeting\n\
+//!      \n\
+//!      SGVsbG8sIHdvcmxk\n\
+//!      =iolR\n\
+//!      -----END PGP MESSAGE-----\n"
+//! );
+//! assert_eq!(armor::decode(&armored).unwrap().data(), b"Hello, world");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::pem::{boundary_label, Line, Lines};
+use crate::{ArmorError, Base64Engine, DecodeIgnore, LineEnding};
+
+const BODY: Base64Engine = Base64Engine::STANDARD
+    .with_line_wrap(76, LineEnding::Lf)
+    .with_decode_ignore(DecodeIgnore::Whitespace);
+
+const CRC24_INIT: u32 = 0xb704ce;
+const CRC24_POLY: u32 = 0x1864cfb;
+
+static CRC24_TABLE: [u32; 256] = crc24_table();
+
+const fn crc24_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u32) << 16;
+        let mut bit = 0;
+        while bit < 8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+            bit += 1;
+        }
+        table[i] = crc & 0xffffff;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-24 of `data` as defined by RFC 4880 §6.1.
+pub fn crc24(data: &[u8]) -> u32 {
+    data.iter().fold(CRC24_INIT, |crc, &byte| {
+        ((crc << 8) ^ CRC24_TABLE[((crc >> 16) as u8 ^ byte) as usize]) & 0xffffff
+    })
+}
+
+/// Type of armored data, named on the BEGIN and END lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArmorKind {
+    /// `PGP MESSAGE`
+    Message,
+    /// `PGP PUBLIC KEY BLOCK`
+    PublicKey,
+    /// `PGP PRIVATE KEY BLOCK`
+    PrivateKey,
+    /// `PGP SIGNATURE`
+    Signature,
+    /// `PGP MESSAGE, PART X/Y`, or `PGP MESSAGE, PART X` when the number of
+    /// parts is not known.
+    MessagePart { part: u32, total: Option<u32> },
+}
+
+impl ArmorKind {
+    fn parse(label: &str) -> Option<ArmorKind> {
+        let kind = match label {
+            "PGP MESSAGE" => ArmorKind::Message,
+            "PGP PUBLIC KEY BLOCK" => ArmorKind::PublicKey,
+            "PGP PRIVATE KEY BLOCK" => ArmorKind::PrivateKey,
+            "PGP SIGNATURE" => ArmorKind::Signature,
+            _ => {
+                let part = label.strip_prefix("PGP MESSAGE, PART ")?;
+                match part.split_once('/') {
+                    Some((part, total)) => ArmorKind::MessagePart {
+                        part: part.parse().ok()?,
+                        total: Some(total.parse().ok()?),
+                    },
+                    None => ArmorKind::MessagePart {
+                        part: part.parse().ok()?,
+                        total: None,
+                    },
+                }
+            }
+        };
+        Some(kind)
+    }
+}
+
+impl fmt::Display for ArmorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArmorKind::Message => write!(f, "PGP MESSAGE"),
+            ArmorKind::PublicKey => write!(f, "PGP PUBLIC KEY BLOCK"),
+            ArmorKind::PrivateKey => write!(f, "PGP PRIVATE KEY BLOCK"),
+            ArmorKind::Signature => write!(f, "PGP SIGNATURE"),
+            ArmorKind::MessagePart {
+                part,
+                total: Some(total),
+            } => write!(f, "PGP MESSAGE, PART {}/{}", part, total),
+            ArmorKind::MessagePart { part, total: None } => {
+                write!(f, "PGP MESSAGE, PART {}", part)
+            }
+        }
+    }
+}
+
+/// Armored OpenPGP data: its kind, armor headers and the binary data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Armor {
+    kind: ArmorKind,
+    headers: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl Armor {
+    pub fn new(kind: ArmorKind, data: impl Into<Vec<u8>>) -> Armor {
+        Armor {
+            kind,
+            headers: Vec::new(),
+            data: data.into(),
+        }
+    }
+    /// Adds a `Key: Value` armor header such as `Version` or `Comment`;
+    /// headers are written in the order added.
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Armor {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+    pub fn kind(&self) -> ArmorKind {
+        self.kind
+    }
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+    /// Value of the first header called `key`. Keys are case sensitive.
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+    /// Writes the armor with its body wrapped at 76 columns, followed by the
+    /// checksum line. Every line is ended by `'\n'`.
+    pub fn encode(&self) -> String {
+        let label = self.kind.to_string();
+        let mut output = String::new();
+        output.push_str("-----BEGIN ");
+        output.push_str(&label);
+        output.push_str("-----\n");
+        for (key, value) in &self.headers {
+            output.push_str(key);
+            output.push_str(": ");
+            output.push_str(value);
+            output.push('\n');
+        }
+        output.push('\n');
+        if !self.data.is_empty() {
+            BODY.encode_append(&self.data, &mut output);
+            output.push('\n');
+        }
+        output.push('=');
+        Base64Engine::STANDARD.encode_append(&crc24(&self.data).to_be_bytes()[1..], &mut output);
+        output.push_str("\n-----END ");
+        output.push_str(&label);
+        output.push_str("-----\n");
+        output
+    }
+}
+
+/// Decodes the first armor of `input`, ignoring any text around it, and
+/// verifies its checksum.
+///
+/// BEGIN lines of other types are skipped too, so a cleartext-signed
+/// message gives its `PGP SIGNATURE` armor.
+pub fn decode(input: &str) -> Result<Armor, ArmorError> {
+    let mut lines = Lines::new(input);
+    let (label, kind, begin_line) = loop {
+        let line = lines.next().ok_or(ArmorError::MissingBegin)?;
+        if let Some(label) = boundary_label(line.text, "-----BEGIN ").flatten() {
+            if let Some(kind) = ArmorKind::parse(label) {
+                break (label, kind, line.number);
+            }
+        }
+    };
+    let missing_end = ArmorError::MissingEnd { line: begin_line };
+    let mut armor = Armor::new(kind, Vec::new());
+
+    // armor headers, ended by a blank line even when there are none
+    loop {
+        let line = lines.next().ok_or(missing_end)?;
+        if line.text.trim().is_empty() {
+            break;
+        }
+        let (key, value) = line
+            .text
+            .split_once(':')
+            .filter(|(key, _)| !key.is_empty() && !key.contains(char::is_whitespace))
+            .ok_or(ArmorError::InvalidHeader { line: line.number })?;
+        armor.headers.push((key.into(), value.trim().into()));
+    }
+
+    let mut body_start = None;
+    let mut checksum: Option<(u32, Line<'_>)> = None;
+    let end = loop {
+        let line = lines.next().ok_or(missing_end)?;
+        if let Some(end) = boundary_label(line.text, "-----END ") {
+            let end = end.ok_or(ArmorError::InvalidLabel { line: line.number })?;
+            if end != label {
+                return Err(ArmorError::LabelMismatch { line: line.number });
+            }
+            break line;
+        }
+        if let Some((_, checksum_line)) = &checksum {
+            // the checksum line must be the last one before END
+            return Err(ArmorError::InvalidChecksum {
+                line: checksum_line.number,
+            });
+        }
+        // Base64 lines never start with padding, so "=XXXX" is the checksum
+        if let Some(crc) = line.text.trim_end().strip_prefix('=') {
+            let crc = Base64Engine::STANDARD
+                .decode(crc)
+                .ok()
+                .filter(|crc| crc.len() == 3)
+                .ok_or(ArmorError::InvalidChecksum { line: line.number })?;
+            let crc = crc
+                .iter()
+                .fold(0u32, |crc, &byte| (crc << 8) | u32::from(byte));
+            checksum = Some((crc, line));
+            continue;
+        }
+        body_start.get_or_insert(line.start);
+    };
+
+    let (found, checksum_line) =
+        checksum.ok_or(ArmorError::MissingChecksum { line: end.number })?;
+    let start = body_start.unwrap_or(checksum_line.start);
+    armor.data = BODY
+        .decode(&input[start..checksum_line.start])
+        .map_err(|e| ArmorError::InvalidBase64(e.offset_by(start)))?;
+
+    let expected = crc24(&armor.data);
+    if found != expected {
+        return Err(ArmorError::ChecksumMismatch { expected, found });
+    }
+    Ok(armor)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::DecodeError;
+
+    #[test]
+    fn crc24_check() {
+        assert_eq!(crc24(b""), CRC24_INIT);
+        assert_eq!(crc24(b"123456789"), 0x21cf02);
+    }
+
+    #[test]
+    fn round_trip() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for kind in [
+            ArmorKind::Message,
+            ArmorKind::PublicKey,
+            ArmorKind::PrivateKey,
+            ArmorKind::Signature,
+            ArmorKind::MessagePart {
+                part: 2,
+                total: Some(3),
+            },
+            ArmorKind::MessagePart {
+                part: 1,
+                total: None,
+            },
+        ] {
+            let armor = Armor::new(kind, data.clone()).with_header("Version", "simple-encode");
+            let text = armor.encode();
+            assert!(text.lines().all(|line| line.len() <= 76));
+            assert_eq!(decode(&text).unwrap(), armor);
+        }
+
+        let empty = Armor::new(ArmorKind::Signature, Vec::new());
+        assert_eq!(
+            empty.encode(),
+            "-----BEGIN PGP SIGNATURE-----\n\n=twTO\n-----END PGP SIGNATURE-----\n"
+        );
+        assert_eq!(decode(&empty.encode()).unwrap(), empty);
+    }
+
+    #[test]
+    fn surrounding_text() {
+        let text = "\
+-----BEGIN PGP SIGNED MESSAGE-----\r
+Hash: SHA256\r
+\r
+Hello\r
+-----BEGIN PGP SIGNATURE-----\r
+Comment: two headers\r
+Version: 1\r
+\r
+SGVsbG8s\r
+IHdvcmxk\r
+=iolR\r
+-----END PGP SIGNATURE-----\r
+";
+        let armor = decode(text).unwrap();
+        assert_eq!(armor.kind(), ArmorKind::Signature);
+        assert_eq!(armor.header("Comment"), Some("two headers"));
+        assert_eq!(armor.header("Version"), Some("1"));
+        assert_eq!(armor.data(), b"Hello, world");
+    }
+
+    #[test]
+    fn errors() {
+        let good = Armor::new(ArmorKind::Message, b"Hello, world".to_vec()).encode();
+        assert_eq!(
+            decode(&good.replace("=iolR", "=iolS")),
+            Err(ArmorError::ChecksumMismatch {
+                expected: 0x8a8951,
+                found: 0x8a8952,
+            })
+        );
+        assert_eq!(
+            decode(&good.replace("=iolR\n", "")),
+            Err(ArmorError::MissingChecksum { line: 4 })
+        );
+        assert_eq!(
+            decode(&good.replace("=iolR", "=iol")),
+            Err(ArmorError::InvalidChecksum { line: 4 })
+        );
+        assert_eq!(
+            decode(&good.replace("END PGP MESSAGE", "END PGP SIGNATURE")),
+            Err(ArmorError::LabelMismatch { line: 5 })
+        );
+        assert_eq!(
+            decode(&good.replace("-----END PGP MESSAGE-----\n", "")),
+            Err(ArmorError::MissingEnd { line: 1 })
+        );
+        assert_eq!(
+            decode(&good.replace("\n\n", "\n")),
+            Err(ArmorError::InvalidHeader { line: 2 })
+        );
+        assert_eq!(
+            decode(&good.replace("SGVs", "SG!s")),
+            Err(ArmorError::InvalidBase64(DecodeError::InvalidCharacter {
+                index: 31,
+                character: '!'
+            }))
+        );
+        assert_eq!(
+            decode(&good.replace("-----END PGP MESSAGE-----", "-----END PGP MESSAGE")),
+            Err(ArmorError::InvalidLabel { line: 5 })
+        );
+        assert_eq!(decode("no armor"), Err(ArmorError::MissingBegin));
+        assert_eq!(
+            decode("-----BEGIN PGP UNKNOWN-----\n\n=twTO\n-----END PGP UNKNOWN-----\n"),
+            Err(ArmorError::MissingBegin)
+        );
+    }
+}
@@ -0,0 +1,526 @@
+//! Bech32 (BIP-173) and Bech32m (BIP-350): a human-readable part, a `1`
+//! separator and 5-bit data protected by a BCH checksum, as used by
+//! `bc1…`, `age1…` and `npub1…` strings.
+//!
+//! ```
+//! use simple_encode::bech32::{self, Variant};
+//!
+//! // segwit v0 address: witness version, then the 20-byte program
+//! let program = [
+//!     0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3,
+//!     0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6,
+//! ];
+//! let mut data = vec![0];
+//! data.extend(bech32::to_base32(&program));
+//! let address = bech32::encode("bc", &data, Variant::Bech32).unwrap();
+//! assert_eq!(address, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+//!
+//! let (hrp, data, variant) = bech32::decode("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4").unwrap();
+//! assert_eq!((hrp.as_str(), variant), ("bc", Variant::Bech32));
+//! assert_eq!(bech32::from_base32(&data[1..]).unwrap(), program);
+//! ```
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::bits::Regroup;
+use crate::{Alphabet, Bech32Error};
+
+static BECH32_ALPHABET: Alphabet<32> = Alphabet::new("qpzry9x8gf2tvdw0s3jn54khce6mua7l");
+
+/// Longest string BIP-173 allows; use [`encode_with_limit`] and
+/// [`decode_with_limit`] for formats such as Lightning invoices that go
+/// beyond it.
+pub const MAX_LENGTH: usize = 90;
+
+const MAX_HRP_LENGTH: usize = 83;
+const CHECKSUM_LENGTH: usize = 6;
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+/// Longest data part in which every single substitution changes the
+/// remainder differently.
+const MAX_LOCATED_LENGTH: usize = 1023;
+
+/// Which checksum constant a string uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// BIP-173, used by segwit v0 addresses.
+    Bech32,
+    /// BIP-350, used by segwit v1+ addresses and most newer formats.
+    Bech32m,
+}
+
+impl Variant {
+    const fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+/// Encodes `hrp` and the 5-bit values of `data`, e.g. from [`to_base32`],
+/// into a lowercase string of at most [`MAX_LENGTH`] characters.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, Bech32Error> {
+    encode_with_limit(hrp, data, variant, MAX_LENGTH)
+}
+
+/// Like [`encode`], with a length limit of `limit` characters.
+pub fn encode_with_limit(
+    hrp: &str,
+    data: &[u8],
+    variant: Variant,
+    limit: usize,
+) -> Result<String, Bech32Error> {
+    let hrp = check_hrp(hrp)?;
+    if let Some(index) = data.iter().position(|&value| value >= 32) {
+        return Err(Bech32Error::InvalidData { index });
+    }
+    let length = hrp.len() + 1 + data.len() + CHECKSUM_LENGTH;
+    if length > limit {
+        return Err(Bech32Error::InvalidLength { length });
+    }
+
+    let checksum = checksum(hrp.as_bytes(), data, variant);
+    let mut output = String::with_capacity(length);
+    output.push_str(&hrp);
+    output.push('1');
+    for &value in data.iter().chain(&checksum) {
+        output.push(BECH32_ALPHABET.symbol(value as usize) as char);
+    }
+    Ok(output)
+}
+
+/// Decodes a string of at most [`MAX_LENGTH`] characters into its lowercase
+/// human-readable part, its 5-bit data without the checksum and the variant
+/// whose checksum it carries.
+pub fn decode(input: &str) -> Result<(String, Vec<u8>, Variant), Bech32Error> {
+    decode_with_limit(input, MAX_LENGTH)
+}
+
+/// Like [`decode`], with a length limit of `limit` characters.
+pub fn decode_with_limit(
+    input: &str,
+    limit: usize,
+) -> Result<(String, Vec<u8>, Variant), Bech32Error> {
+    if input.len() > limit {
+        return Err(Bech32Error::InvalidLength {
+            length: input.len(),
+        });
+    }
+    let (hrp, data) = split(input)?;
+    let hrp = check_hrp(hrp)?;
+    if data.len() < CHECKSUM_LENGTH {
+        return Err(Bech32Error::InvalidLength {
+            length: input.len(),
+        });
+    }
+
+    let data_start = input.len() - data.len();
+    let mut values = Vec::with_capacity(data.len());
+    for (i, &byte) in data.as_bytes().iter().enumerate() {
+        match BECH32_ALPHABET.value(byte.to_ascii_lowercase()) {
+            Some(value) => values.push(value),
+            None => {
+                return Err(Bech32Error::InvalidCharacter {
+                    index: data_start + i,
+                    character: byte as char,
+                })
+            }
+        }
+    }
+
+    let residue = polymod(hrp_expand(hrp.as_bytes()).chain(values.iter().copied()));
+    let variant = match residue {
+        c if c == Variant::Bech32.constant() => Variant::Bech32,
+        c if c == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err(locate_errors(input, data_start, &values, residue)),
+    };
+    values.truncate(values.len() - CHECKSUM_LENGTH);
+    Ok((hrp, values, variant))
+}
+
+/// Regroups bytes into 5-bit values, padding the last one with zero bits.
+pub fn to_base32(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity((input.len() * 8).div_ceil(5));
+    let mut bits = Regroup::<8, 5>::new();
+    for &byte in input {
+        bits.push(byte, |value| output.push(value));
+    }
+    bits.flush(|value| output.push(value));
+    output
+}
+
+/// Regroups 5-bit values into bytes. Fewer than 5 bits may be left over,
+/// and only if they are zero.
+pub fn from_base32(input: &[u8]) -> Result<Vec<u8>, Bech32Error> {
+    let mut output = Vec::with_capacity(input.len() * 5 / 8);
+    let mut bits = Regroup::<5, 8>::new();
+
+    for (index, &value) in input.iter().enumerate() {
+        if value >= 32 {
+            return Err(Bech32Error::InvalidData { index });
+        }
+        bits.push(value, |byte| output.push(byte));
+    }
+
+    if bits.leftover() >= 5 || bits.has_trailing_bits() {
+        return Err(Bech32Error::InvalidPadding);
+    }
+    Ok(output)
+}
+
+/// Splits `input` at its last `'1'` after checking that it has only
+/// characters from US-ASCII 33 to 126, all in the same case.
+fn split(input: &str) -> Result<(&str, &str), Bech32Error> {
+    if let Some(index) = input.bytes().position(|b| !(33..=126).contains(&b)) {
+        return Err(Bech32Error::InvalidCharacter {
+            index,
+            character: input[index..].chars().next().unwrap_or_default(),
+        });
+    }
+    if input.bytes().any(|b| b.is_ascii_lowercase())
+        && input.bytes().any(|b| b.is_ascii_uppercase())
+    {
+        return Err(Bech32Error::MixedCase);
+    }
+    let separator = input.rfind('1').ok_or(Bech32Error::MissingSeparator)?;
+    Ok((&input[..separator], &input[separator + 1..]))
+}
+
+/// Lowercase copy of a valid human-readable part.
+fn check_hrp(hrp: &str) -> Result<String, Bech32Error> {
+    if hrp.is_empty() || hrp.len() > MAX_HRP_LENGTH {
+        return Err(Bech32Error::InvalidHrp);
+    }
+    if let Some(index) = hrp.bytes().position(|b| !(33..=126).contains(&b)) {
+        return Err(Bech32Error::InvalidCharacter {
+            index,
+            character: hrp[index..].chars().next().unwrap_or_default(),
+        });
+    }
+    if hrp.bytes().any(|b| b.is_ascii_lowercase()) && hrp.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Err(Bech32Error::MixedCase);
+    }
+    Ok(hrp.to_ascii_lowercase())
+}
+
+fn checksum(hrp: &[u8], data: &[u8], variant: Variant) -> [u8; CHECKSUM_LENGTH] {
+    let values = hrp_expand(hrp)
+        .chain(data.iter().copied())
+        .chain([0; CHECKSUM_LENGTH]);
+    let residue = polymod(values) ^ variant.constant();
+    core::array::from_fn(|i| ((residue >> (5 * (5 - i))) & 0b11111) as u8)
+}
+
+/// The high bits of every character of `hrp`, a zero, then the low bits.
+fn hrp_expand(hrp: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    hrp.iter()
+        .map(|b| b >> 5)
+        .chain([0])
+        .chain(hrp.iter().map(|b| b & 0b11111))
+}
+
+/// Remainder of the BCH code over GF(32) that the checksum is built from.
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    values.into_iter().fold(1, polymod_step)
+}
+
+fn polymod_step(residue: u32, value: u8) -> u32 {
+    let top = residue >> 25;
+    let residue = ((residue & 0x1ffffff) << 5) ^ (value as u32);
+    GENERATOR
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| (top >> i) & 1 != 0)
+        .fold(residue, |residue, (_, g)| residue ^ g)
+}
+
+/// Builds the checksum error for a data part whose `values` leave `residue`,
+/// locating the fewest substitutions, at most two, that give a valid checksum
+/// of either variant.
+///
+/// The remainder is linear in the values, so a substitution adding `e` to the
+/// value at `index` changes the residue by a syndrome that only depends on
+/// `e` and the distance to the end. Every single substitution's syndrome goes
+/// into a sorted table; one substitution matches the residue directly, two
+/// are found by looking up the residue minus the first one's syndrome.
+///
+/// Up to [`MAX_LENGTH`] characters the code has a distance of 5, so two
+/// substitutions are located exactly; longer strings only get the single
+/// substitution search, and data parts past [`MAX_LOCATED_LENGTH`], where
+/// the syndromes repeat, none. Errors in the human-readable part are not
+/// located.
+fn locate_errors(input: &str, data_start: usize, values: &[u8], residue: u32) -> Bech32Error {
+    if values.len() > MAX_LOCATED_LENGTH {
+        return Bech32Error::InvalidChecksum {
+            positions: Vec::new(),
+            candidates: Vec::new(),
+        };
+    }
+    // (syndrome, index, e) for every substitution
+    let mut syndromes = Vec::with_capacity(values.len() * 31);
+    let mut states: [u32; 32] = core::array::from_fn(|e| e as u32);
+    for index in (0..values.len()).rev() {
+        for (e, state) in states.iter_mut().enumerate().skip(1) {
+            syndromes.push((*state, index, e as u8));
+            *state = polymod_step(*state, 0);
+        }
+    }
+    syndromes.sort_unstable();
+    let matching = |syndrome: u32| {
+        let start = syndromes.partition_point(|entry| entry.0 < syndrome);
+        syndromes[start..]
+            .iter()
+            .take_while(move |entry| entry.0 == syndrome)
+    };
+
+    let targets = [Variant::Bech32, Variant::Bech32m].map(|v| residue ^ v.constant());
+    let mut corrections: Vec<Vec<(usize, u8)>> = Vec::new();
+    for target in targets {
+        corrections.extend(matching(target).map(|&(_, index, e)| vec![(index, e)]));
+    }
+    if corrections.is_empty() && input.len() <= MAX_LENGTH {
+        for target in targets {
+            for &(syndrome, first, e) in &syndromes {
+                let pairs = matching(target ^ syndrome)
+                    .filter(|entry| entry.1 > first)
+                    .map(|&(_, second, f)| vec![(first, e), (second, f)]);
+                corrections.extend(pairs);
+            }
+        }
+    }
+
+    let uppercase = input.bytes().any(|b| b.is_ascii_uppercase());
+    let mut positions: Vec<usize> = corrections
+        .iter()
+        .flatten()
+        .map(|&(index, _)| data_start + index)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    let candidates = corrections
+        .iter()
+        .map(|errors| {
+            let mut corrected: Vec<char> = input.chars().collect();
+            for &(index, e) in errors {
+                let symbol = BECH32_ALPHABET.symbol((values[index] ^ e) as usize);
+                corrected[data_start + index] = if uppercase {
+                    symbol.to_ascii_uppercase() as char
+                } else {
+                    symbol as char
+                };
+            }
+            corrected.into_iter().collect()
+        })
+        .collect();
+    Bech32Error::InvalidChecksum {
+        positions,
+        candidates,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let long = |hrp: &str, symbol: &str, checksum: &str| {
+            format!("{}1{}{}", hrp, symbol.repeat(82), checksum)
+        };
+        let bech32 = [
+            String::from("A12UEL5L"),
+            "a12uel5l".into(),
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs".into(),
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw".into(),
+            long("1", "q", "c8247j"),
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w".into(),
+            "?1ezyfcl".into(),
+        ];
+        let bech32m = [
+            String::from("A1LQFN3A"),
+            "a1lqfn3a".into(),
+            "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6".into(),
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx".into(),
+            long("1", "l", "ludsr8"),
+            "split1checkupstagehandshakeupstreamerranterredcaperredlc445v".into(),
+            "?1v759aa".into(),
+        ];
+        for (strings, expected) in [(bech32, Variant::Bech32), (bech32m, Variant::Bech32m)] {
+            for s in strings {
+                let (hrp, data, variant) = decode(&s).unwrap();
+                assert_eq!(variant, expected, "{}", s);
+                assert_eq!(encode(&hrp, &data, variant).unwrap(), s.to_lowercase());
+            }
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            decode("A1G7SGD8"),
+            Err(Bech32Error::InvalidChecksum { .. })
+        ));
+        assert_eq!(
+            decode("li1dgmt3"),
+            Err(Bech32Error::InvalidLength { length: 8 })
+        );
+        assert_eq!(decode("pzry9x0s0muk"), Err(Bech32Error::MissingSeparator));
+        assert_eq!(decode("1pzry9x0s0muk"), Err(Bech32Error::InvalidHrp));
+        assert_eq!(decode("10a06t8"), Err(Bech32Error::InvalidHrp));
+        assert_eq!(
+            decode("x1b4n0q5v"),
+            Err(Bech32Error::InvalidCharacter {
+                index: 2,
+                character: 'b'
+            })
+        );
+        assert_eq!(
+            decode("\x7f1axkwrx"),
+            Err(Bech32Error::InvalidCharacter {
+                index: 0,
+                character: '\x7f'
+            })
+        );
+        assert_eq!(decode("A12uEL5L"), Err(Bech32Error::MixedCase));
+        assert_eq!(
+            decode("an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx"),
+            Err(Bech32Error::InvalidLength { length: 91 })
+        );
+    }
+
+    #[test]
+    fn error_location() {
+        let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        let typo = |changes: &[(usize, char)]| {
+            let mut chars: Vec<char> = address.chars().collect();
+            for &(index, c) in changes {
+                chars[index] = c;
+            }
+            chars.into_iter().collect::<String>()
+        };
+
+        for changes in [
+            &[(5, 'x')][..],
+            &[(41, 'q')],
+            &[(4, '5'), (40, 'x')],
+            &[(10, 'z'), (11, 'z')],
+        ] {
+            let mistyped = typo(changes);
+            let mut positions: Vec<usize> = changes.iter().map(|&(index, _)| index).collect();
+            positions.sort_unstable();
+            assert_eq!(
+                decode(&mistyped),
+                Err(Bech32Error::InvalidChecksum {
+                    positions,
+                    candidates: vec![address.into()],
+                }),
+                "{}",
+                mistyped
+            );
+        }
+
+        assert_eq!(
+            decode("A12UEL5M"),
+            Err(Bech32Error::InvalidChecksum {
+                positions: vec![7],
+                candidates: vec!["A12UEL5L".into()],
+            })
+        );
+
+        // a Bech32m string is corrected to Bech32m
+        assert_eq!(
+            decode("a1lqfn3q"),
+            Err(Bech32Error::InvalidChecksum {
+                positions: vec![7],
+                candidates: vec!["a1lqfn3a".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn long_error_location() {
+        let data: Vec<u8> = (0..803).map(|i| (i * 7 % 32) as u8).collect();
+        let valid = encode_with_limit("a", &data, Variant::Bech32m, 1023).unwrap();
+        assert_eq!(valid.len(), 811);
+        let typo = |changes: &[(usize, char)]| {
+            let mut chars: Vec<char> = valid.chars().collect();
+            for &(index, c) in changes {
+                chars[index] = if chars[index] == c { 'q' } else { c };
+            }
+            chars.into_iter().collect::<String>()
+        };
+
+        // one substitution is still located past 90 characters
+        assert_eq!(
+            decode_with_limit(&typo(&[(300, 'x')]), 1023),
+            Err(Bech32Error::InvalidChecksum {
+                positions: vec![300],
+                candidates: vec![valid.clone()],
+            })
+        );
+        // two are not, as the checksum no longer tells them apart
+        for changes in [
+            &[(10, 'x'), (300, 'x')][..],
+            &[(10, 'x'), (300, 'x'), (500, 'x')],
+        ] {
+            assert_eq!(
+                decode_with_limit(&typo(changes), 1023),
+                Err(Bech32Error::InvalidChecksum {
+                    positions: vec![],
+                    candidates: vec![],
+                })
+            );
+        }
+
+        let data = vec![0; 1100];
+        let valid = encode_with_limit("a", &data, Variant::Bech32, 2000).unwrap();
+        let mistyped = valid.replacen('q', "x", 1);
+        assert_eq!(
+            decode_with_limit(&mistyped, 2000),
+            Err(Bech32Error::InvalidChecksum {
+                positions: vec![],
+                candidates: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn encode_errors() {
+        assert_eq!(
+            encode("", &[], Variant::Bech32),
+            Err(Bech32Error::InvalidHrp)
+        );
+        assert_eq!(
+            encode("Bc", &[], Variant::Bech32),
+            Err(Bech32Error::MixedCase)
+        );
+        assert_eq!(
+            encode("bc", &[0, 32], Variant::Bech32),
+            Err(Bech32Error::InvalidData { index: 1 })
+        );
+        let data = [0; 82];
+        assert!(encode("a", &data, Variant::Bech32m).is_ok());
+        assert_eq!(
+            encode("ab", &data, Variant::Bech32m),
+            Err(Bech32Error::InvalidLength { length: 91 })
+        );
+        assert!(encode_with_limit("ab", &data, Variant::Bech32m, 1023).is_ok());
+    }
+
+    #[test]
+    fn regrouping() {
+        for len in 0usize..12 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 5) as u8).collect();
+            let values = to_base32(&bytes);
+            assert_eq!(values.len(), (len * 8).div_ceil(5));
+            assert_eq!(from_base32(&values).unwrap(), bytes);
+        }
+        assert_eq!(to_base32(&[0xff]), [31, 28]);
+        assert_eq!(from_base32(&[31, 29]), Err(Bech32Error::InvalidPadding));
+        assert_eq!(from_base32(&[0, 0, 0]), Err(Bech32Error::InvalidPadding));
+        assert_eq!(
+            from_base32(&[0, 32]),
+            Err(Bech32Error::InvalidData { index: 1 })
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! Regrouping of a bit stream between value widths, shared by Base32 and
+//! bech32.
+
+/// Accumulates `FROM`-bit values and hands them out again as `TO`-bit ones,
+/// most significant bits first.
+pub(crate) struct Regroup<const FROM: u32, const TO: u32> {
+    /// bits not handed out yet, in the low `bits` bits
+    buffer: u32,
+    bits: u32,
+}
+
+impl<const FROM: u32, const TO: u32> Regroup<FROM, TO> {
+    const MASK: u32 = (1 << TO) - 1;
+
+    pub(crate) const fn new() -> Self {
+        Regroup { buffer: 0, bits: 0 }
+    }
+    /// Appends `value` and passes every complete `TO`-bit group to `emit`.
+    pub(crate) fn push(&mut self, value: u8, mut emit: impl FnMut(u8)) {
+        self.buffer = (self.buffer << FROM) | value as u32;
+        self.bits += FROM;
+        while self.bits >= TO {
+            self.bits -= TO;
+            emit(((self.buffer >> self.bits) & Self::MASK) as u8);
+        }
+        self.buffer &= (1 << self.bits) - 1;
+    }
+    /// Passes the incomplete last group to `emit`, filled up with zero bits.
+    pub(crate) fn flush(&self, mut emit: impl FnMut(u8)) {
+        if self.bits > 0 {
+            emit((self.buffer << (TO - self.bits)) as u8);
+        }
+    }
+    /// Number of bits left over that do not make up a whole group.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn leftover(&self) -> u32 {
+        self.bits
+    }
+    /// Whether any of the left over bits is set, which a canonical encoder
+    /// never does.
+    pub(crate) const fn has_trailing_bits(&self) -> bool {
+        self.buffer != 0
+    }
+}
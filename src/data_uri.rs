@@ -0,0 +1,361 @@
+//! `data:` URIs (RFC 2397), which carry a media type and their data inline.
+//!
+//! ```
+//! use simple_encode::data_uri::{self, DataUri};
+//!
+//! let uri = DataUri::new("text/plain", b"Hello, world".to_vec())
+//!     .with_parameter("charset", "utf-8")
+//!     .encode();
+//! assert_eq!(uri, "data:text/plain;charset=utf-8,Hello,%20world");
+//!
+//! let parsed = data_uri::parse("data:image/png;base64,iVBORw0KGgo=").unwrap();
+//! assert_eq!(parsed.media_type(), "image/png");
+//! assert_eq!(parsed.data(), b"\x89PNG\r\n\x1a\n");
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{Base64, Base64Engine, DataUriError, DecodeError, DecodeIgnore, DecodePadding};
+
+/// Base64 as browsers accept it in `data:` URIs, with optional padding and
+/// whitespace.
+const BASE64: Base64Engine = Base64Engine::STANDARD
+    .with_decode_padding(DecodePadding::Optional)
+    .with_decode_ignore(DecodeIgnore::Whitespace);
+
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// How [`DataUri::encode_with`] writes the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataEncoding {
+    /// `;base64` followed by Base64, compact for binary data.
+    Base64,
+    /// The data itself with other bytes than URL-safe ASCII escaped as `%XX`,
+    /// compact for text.
+    Percent,
+}
+
+/// Media type, parameters and data of a `data:` URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataUri {
+    media_type: String,
+    parameters: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl DataUri {
+    /// URI holding `data` of `media_type`, e.g. `"image/png"`. An empty
+    /// media type stands for the RFC 2397 default, which
+    /// [`encode`](DataUri::encode) writes out as `text/plain` with a
+    /// `US-ASCII` charset unless one is given.
+    pub fn new(media_type: impl Into<String>, data: impl Into<Vec<u8>>) -> DataUri {
+        DataUri {
+            media_type: media_type.into(),
+            parameters: Vec::new(),
+            data: data.into(),
+        }
+    }
+    /// Adds a media type parameter such as `charset`; parameters are
+    /// written in the order added.
+    pub fn with_parameter(mut self, name: impl Into<String>, value: impl Into<String>) -> DataUri {
+        self.parameters.push((name.into(), value.into()));
+        self
+    }
+    /// The media type without parameters, `"text/plain"` when a parsed URI
+    /// leaves it out.
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+    pub fn parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+    /// Value of the first parameter called `name`, ignoring ASCII case.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+    /// The `charset` parameter, `"US-ASCII"` when a parsed URI leaves out
+    /// the media type.
+    pub fn charset(&self) -> Option<&str> {
+        self.parameter("charset")
+    }
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+    /// Writes the URI with whichever of Base64 and percent-encoding gives
+    /// the shorter result.
+    pub fn encode(&self) -> String {
+        let percent: usize = self
+            .data
+            .iter()
+            .map(|&byte| if is_data_char(byte) { 1 } else { 3 })
+            .sum();
+        let base64 = ";base64".len() + Base64::encoded_len(self.data.len());
+        if base64 < percent {
+            self.encode_with(DataEncoding::Base64)
+        } else {
+            self.encode_with(DataEncoding::Percent)
+        }
+    }
+    /// Writes the URI with the data in the given encoding.
+    pub fn encode_with(&self, encoding: DataEncoding) -> String {
+        let mut output = String::from("data:");
+        if self.media_type.is_empty() {
+            output.push_str("text/plain");
+            if self.charset().is_none() {
+                output.push_str(";charset=US-ASCII");
+            }
+        } else {
+            output.push_str(&self.media_type);
+        }
+        for (name, value) in &self.parameters {
+            output.push(';');
+            push_percent_encoded(name.as_bytes(), &mut output, is_unreserved);
+            output.push('=');
+            push_percent_encoded(value.as_bytes(), &mut output, is_unreserved);
+        }
+        match encoding {
+            DataEncoding::Base64 => {
+                output.push_str(";base64,");
+                Base64::encode_append(&self.data, &mut output);
+            }
+            DataEncoding::Percent => {
+                output.push(',');
+                push_percent_encoded(&self.data, &mut output, is_data_char);
+            }
+        }
+        output
+    }
+}
+
+/// Parses a `data:` URI, decoding its data from Base64 or percent-encoding.
+pub fn parse(uri: &str) -> Result<DataUri, DataUriError> {
+    let rest = uri
+        .get(..5)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("data:"))
+        .map(|_| &uri[5..])
+        .ok_or(DataUriError::MissingScheme)?;
+    let (header, data) = rest.split_once(',').ok_or(DataUriError::MissingComma)?;
+    let data_start = uri.len() - data.len();
+
+    let mut segments = header.split(';');
+    let media_type = segments.next().unwrap_or_default();
+    let mut offset = 5 + media_type.len();
+    let mut uri_data = if media_type.is_empty() {
+        DataUri::new("text/plain", Vec::new())
+    } else {
+        let valid = media_type
+            .split_once('/')
+            .filter(|(kind, subtype)| is_token(kind) && is_token(subtype));
+        if valid.is_none() {
+            return Err(DataUriError::InvalidMediaType);
+        }
+        DataUri::new(media_type, Vec::new())
+    };
+
+    let mut base64 = false;
+    let mut segments = segments.peekable();
+    while let Some(segment) = segments.next() {
+        let index = offset + 1;
+        offset += 1 + segment.len();
+        if segments.peek().is_none() && segment.eq_ignore_ascii_case("base64") {
+            base64 = true;
+            break;
+        }
+        let (name, value) = segment
+            .split_once('=')
+            .filter(|(name, _)| is_token(name))
+            .ok_or(DataUriError::InvalidParameter { index })?;
+        let name = percent_decode(name, index)?;
+        let value = percent_decode(value, index + segment.len() - value.len())?;
+        let (Ok(name), Ok(value)) = (String::from_utf8(name), String::from_utf8(value)) else {
+            return Err(DataUriError::InvalidParameter { index });
+        };
+        uri_data.parameters.push((name, value));
+    }
+    if media_type.is_empty() && uri_data.charset().is_none() {
+        uri_data
+            .parameters
+            .push(("charset".into(), "US-ASCII".into()));
+    }
+
+    let bytes = percent_decode(data, data_start)?;
+    uri_data.data = if base64 {
+        let text = core::str::from_utf8(&bytes).map_err(|e| {
+            DataUriError::InvalidBase64(DecodeError::invalid_byte(&bytes, e.valid_up_to()))
+        })?;
+        BASE64.decode(text).map_err(DataUriError::InvalidBase64)?
+    } else {
+        bytes
+    };
+    Ok(uri_data)
+}
+
+/// RFC 2045 token, as used for the media type and parameter names.
+fn is_token(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?=".contains(&b))
+}
+
+/// RFC 3986 unreserved characters, which never need escaping.
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-._~".contains(&byte)
+}
+
+/// Characters left as they are in percent-encoded data. Quotes, parentheses
+/// and `%` are escaped so the URI can be embedded in HTML and CSS as is.
+fn is_data_char(byte: u8) -> bool {
+    is_unreserved(byte) || b"!$&*+,/:;=?@".contains(&byte)
+}
+
+fn push_percent_encoded(input: &[u8], output: &mut String, keep: fn(u8) -> bool) {
+    for &byte in input {
+        if keep(byte) {
+            output.push(byte as char);
+        } else {
+            output.push('%');
+            output.push(HEX[(byte >> 4) as usize] as char);
+            output.push(HEX[(byte & 0x0f) as usize] as char);
+        }
+    }
+}
+
+/// Decodes the `%XX` escapes of `input`, which starts at byte `offset` of
+/// the URI.
+fn percent_decode(input: &str, offset: usize) -> Result<Vec<u8>, DataUriError> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            decoded.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        let hex = |j: usize| bytes.get(j).and_then(|&b| crate::BASE16_ALPHABET.value(b));
+        match (hex(i + 1), hex(i + 2)) {
+            (Some(high), Some(low)) => decoded.push((high << 4) | low),
+            _ => {
+                return Err(DataUriError::InvalidPercentEncoding { index: offset + i });
+            }
+        }
+        i += 3;
+    }
+    Ok(decoded)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc2397_examples() {
+        let note = parse("data:,A%20brief%20note").unwrap();
+        assert_eq!(note.media_type(), "text/plain");
+        assert_eq!(note.charset(), Some("US-ASCII"));
+        assert_eq!(note.data(), b"A brief note");
+
+        let greek = parse("data:text/plain;charset=iso-8859-7,%be%d3%be").unwrap();
+        assert_eq!(greek.charset(), Some("iso-8859-7"));
+        assert_eq!(greek.data(), [0xbe, 0xd3, 0xbe]);
+
+        let charset_only = parse("data:;charset=utf-8,%E2%82%AC").unwrap();
+        assert_eq!(charset_only.media_type(), "text/plain");
+        assert_eq!(charset_only.charset(), Some("utf-8"));
+        assert_eq!(charset_only.data(), "€".as_bytes());
+
+        let gif =
+            parse("data:image/gif;base64,R0lGODlhAQABAIAAAAAAAP///yH5BAEAAAAALAAAAAABAAEAAAIBRAA7")
+                .unwrap();
+        assert_eq!(gif.media_type(), "image/gif");
+        assert_eq!(gif.charset(), None);
+        assert!(gif.data().starts_with(b"GIF89a"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let png = DataUri::new("image/png", (0..=255).collect::<Vec<u8>>());
+        let uri = png.encode();
+        assert!(uri.starts_with("data:image/png;base64,"));
+        assert_eq!(parse(&uri).unwrap(), png);
+
+        let text = DataUri::new("text/html", b"<p class=\"x\">50% off</p>".to_vec())
+            .with_parameter("charset", "utf-8");
+        assert_eq!(
+            text.encode(),
+            "data:text/html;charset=utf-8;base64,PHAgY2xhc3M9IngiPjUwJSBvZmY8L3A+"
+        );
+        assert_eq!(
+            text.encode_with(DataEncoding::Percent),
+            "data:text/html;charset=utf-8,%3Cp%20class=%22x%22%3E50%25%20off%3C/p%3E"
+        );
+
+        for encoding in [DataEncoding::Base64, DataEncoding::Percent] {
+            let uri = text.encode_with(encoding);
+            assert_eq!(parse(&uri).unwrap(), text);
+        }
+
+        let odd = DataUri::new("text/plain", b"x".to_vec()).with_parameter("file name", "a;b=c");
+        assert_eq!(odd.encode(), "data:text/plain;file%20name=a%3Bb%3Dc,x");
+        assert_eq!(parse(&odd.encode()).unwrap(), odd);
+    }
+
+    #[test]
+    fn default_media_type() {
+        let uri = DataUri::new("", b"hi".to_vec()).encode();
+        assert_eq!(uri, "data:text/plain;charset=US-ASCII,hi");
+        let parsed = parse(&uri).unwrap();
+        assert_eq!(parsed.media_type(), "text/plain");
+        assert_eq!(parsed.charset(), Some("US-ASCII"));
+        assert_eq!(parsed.data(), b"hi");
+
+        let uri = DataUri::new("", b"hi".to_vec())
+            .with_parameter("charset", "utf-8")
+            .encode();
+        assert_eq!(uri, "data:text/plain;charset=utf-8,hi");
+    }
+
+    #[test]
+    fn lenient_base64() {
+        let uri = parse("DATA:text/plain;BASE64,SGVs bG8%3D").unwrap();
+        assert_eq!(uri.data(), b"Hello");
+        let uri = parse("data:text/plain;base64,SGVsbG8").unwrap();
+        assert_eq!(uri.data(), b"Hello");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse("http://example.com"),
+            Err(DataUriError::MissingScheme)
+        );
+        assert_eq!(parse("data:text/plain"), Err(DataUriError::MissingComma));
+        assert_eq!(parse("data:text,abc"), Err(DataUriError::InvalidMediaType));
+        assert_eq!(
+            parse("data:text/plain;charset,abc"),
+            Err(DataUriError::InvalidParameter { index: 16 })
+        );
+        assert_eq!(
+            parse("data:,100%"),
+            Err(DataUriError::InvalidPercentEncoding { index: 9 })
+        );
+        assert_eq!(
+            parse("data:text/plain;charset=%zz,abc"),
+            Err(DataUriError::InvalidPercentEncoding { index: 24 })
+        );
+        assert_eq!(
+            parse("data:;base64,SGk!"),
+            Err(DataUriError::InvalidBase64(DecodeError::InvalidCharacter {
+                index: 3,
+                character: '!'
+            }))
+        );
+    }
+}
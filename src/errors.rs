@@ -1,23 +1,457 @@
-use std::error::Error;
-use std::fmt;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
 
-#[derive(Debug)]
-pub struct DecodeError {
-    pub msg: String,
+/// Error type of the crate, wrapping the error of each operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    Encode(EncodeError),
+    Decode(DecodeError),
+    #[cfg(feature = "alloc")]
+    Pem(PemError),
+    #[cfg(feature = "alloc")]
+    Armor(ArmorError),
+    #[cfg(feature = "alloc")]
+    DataUri(DataUriError),
+    #[cfg(feature = "alloc")]
+    Bech32(Bech32Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Encode(e) => write!(f, "encode error: {}", e),
+            Error::Decode(e) => write!(f, "decode error: {}", e),
+            #[cfg(feature = "alloc")]
+            Error::Pem(e) => write!(f, "PEM error: {}", e),
+            #[cfg(feature = "alloc")]
+            Error::Armor(e) => write!(f, "armor error: {}", e),
+            #[cfg(feature = "alloc")]
+            Error::DataUri(e) => write!(f, "data URI error: {}", e),
+            #[cfg(feature = "alloc")]
+            Error::Bech32(e) => write!(f, "bech32 error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Encode(e) => Some(e),
+            Error::Decode(e) => Some(e),
+            Error::Pem(e) => Some(e),
+            Error::Armor(e) => Some(e),
+            Error::DataUri(e) => Some(e),
+            Error::Bech32(e) => Some(e),
+        }
+    }
+}
+
+impl From<EncodeError> for Error {
+    fn from(e: EncodeError) -> Self {
+        Error::Encode(e)
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(e: DecodeError) -> Self {
+        Error::Decode(e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<PemError> for Error {
+    fn from(e: PemError) -> Self {
+        Error::Pem(e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ArmorError> for Error {
+    fn from(e: ArmorError) -> Self {
+        Error::Armor(e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<DataUriError> for Error {
+    fn from(e: DataUriError) -> Self {
+        Error::DataUri(e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Bech32Error> for Error {
+    fn from(e: Bech32Error) -> Self {
+        Error::Bech32(e)
+    }
+}
+
+/// Reasons an `encode_to_slice` call can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// The output slice is shorter than the `needed` length.
+    BufferTooSmall { needed: usize },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::BufferTooSmall { needed } => {
+                write!(f, "output buffer too small, {} bytes needed", needed)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for EncodeError {}
+
+/// Reasons a `decode` call can fail.
+///
+/// Offsets are byte offsets into the input string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// `character` at `index` is not part of the alphabet.
+    InvalidCharacter { index: usize, character: char },
+    /// The encoder never produces an input of this `length`.
+    InvalidLength { length: usize },
+    /// Padding is missing, misplaced or followed by data at `index`.
+    InvalidPadding { index: usize },
+    /// The symbol at `index` carries non-zero bits that the encoder would
+    /// have left empty, so the input is not in canonical form.
+    InvalidTrailingBits { index: usize },
+    /// The group ending at `index` decodes to a value too large for its bytes.
+    Overflow { index: usize },
+    /// The payload's checksum is `expected`, but the input carries `found`.
+    ChecksumMismatch { expected: u32, found: u32 },
+    /// The output slice of `decode_to_slice` is shorter than `needed` bytes.
+    BufferTooSmall { needed: usize },
 }
 
 impl DecodeError {
-    pub fn new(msg: &str) -> DecodeError {
-        DecodeError {
-            msg: msg.to_string(),
+    /// Builds an [`DecodeError::InvalidCharacter`] for the character starting
+    /// at byte `index` of `input`.
+    pub(crate) fn invalid_character(input: &str, index: usize) -> DecodeError {
+        DecodeError::invalid_byte(input.as_bytes(), index)
+    }
+
+    /// Like [`DecodeError::invalid_character`] for input that may not be
+    /// valid UTF-8, such as a chunk read from a stream. Bytes that do not
+    /// start a complete character are reported as U+FFFD.
+    pub(crate) fn invalid_byte(input: &[u8], index: usize) -> DecodeError {
+        let rest = &input[index..];
+        let valid = match core::str::from_utf8(rest) {
+            Ok(s) => s,
+            Err(e) => core::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default(),
+        };
+        let character = valid.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER);
+        DecodeError::InvalidCharacter { index, character }
+    }
+
+    /// Moves the position of the error `offset` bytes further, for errors
+    /// found in a slice of a larger input.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn offset_by(self, offset: usize) -> DecodeError {
+        match self {
+            DecodeError::InvalidCharacter { index, character } => DecodeError::InvalidCharacter {
+                index: index + offset,
+                character,
+            },
+            DecodeError::InvalidPadding { index } => DecodeError::InvalidPadding {
+                index: index + offset,
+            },
+            DecodeError::InvalidTrailingBits { index } => DecodeError::InvalidTrailingBits {
+                index: index + offset,
+            },
+            DecodeError::Overflow { index } => DecodeError::Overflow {
+                index: index + offset,
+            },
+            e => e,
         }
     }
 }
 
 impl fmt::Display for DecodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.msg)
+        match self {
+            DecodeError::InvalidCharacter { index, character } => {
+                write!(f, "invalid character {:?} at offset {}", character, index)
+            }
+            DecodeError::InvalidLength { length } => write!(f, "invalid input length {}", length),
+            DecodeError::InvalidPadding { index } => {
+                write!(f, "invalid padding at offset {}", index)
+            }
+            DecodeError::InvalidTrailingBits { index } => {
+                write!(f, "non-zero trailing bits at offset {}", index)
+            }
+            DecodeError::Overflow { index } => write!(f, "value overflow at offset {}", index),
+            DecodeError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "checksum mismatch, expected {:#x}, found {:#x}",
+                expected, found
+            ),
+            DecodeError::BufferTooSmall { needed } => {
+                write!(f, "output buffer too small, {} bytes needed", needed)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for DecodeError {}
+
+/// Reasons parsing PEM text can fail.
+///
+/// Lines are numbered from 1.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PemError {
+    /// The input holds no `-----BEGIN` line.
+    MissingBegin,
+    /// The block labelled `label` that starts on `line` has no END line.
+    MissingEnd { label: String, line: usize },
+    /// The END line on `line` names another label than its BEGIN line.
+    LabelMismatch {
+        begin: String,
+        end: String,
+        line: usize,
+    },
+    /// The BEGIN or END line on `line` has a malformed label.
+    InvalidLabel { line: usize },
+    /// The header on `line` is not a `Name: value` pair.
+    InvalidHeader { line: usize },
+    /// The body is not valid Base64; offsets are relative to the whole input.
+    InvalidBase64(DecodeError),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for PemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PemError::MissingBegin => write!(f, "no PEM block found"),
+            PemError::MissingEnd { label, line } => write!(
+                f,
+                "missing END line for {:?} block started on line {}",
+                label, line
+            ),
+            PemError::LabelMismatch { begin, end, line } => write!(
+                f,
+                "END label {:?} on line {} does not match BEGIN label {:?}",
+                end, line, begin
+            ),
+            PemError::InvalidLabel { line } => write!(f, "invalid PEM label on line {}", line),
+            PemError::InvalidHeader { line } => write!(f, "invalid PEM header on line {}", line),
+            PemError::InvalidBase64(e) => write!(f, "invalid PEM body: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for PemError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PemError::InvalidBase64(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Reasons decoding OpenPGP ASCII armor can fail.
+///
+/// Lines are numbered from 1.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArmorError {
+    /// The input holds no `-----BEGIN PGP` line.
+    MissingBegin,
+    /// The armor starting on `line` has no END line.
+    MissingEnd { line: usize },
+    /// The END line on `line` has a malformed label.
+    InvalidLabel { line: usize },
+    /// The END line on `line` names another armor type than the BEGIN line.
+    LabelMismatch { line: usize },
+    /// The armor header on `line` is not a `Key: Value` pair, or the blank
+    /// line ending the headers is missing.
+    InvalidHeader { line: usize },
+    /// The `=XXXX` checksum line is missing before the END line on `line`.
+    MissingChecksum { line: usize },
+    /// The checksum line on `line` is not `=` followed by four Base64 symbols.
+    InvalidChecksum { line: usize },
+    /// The CRC-24 of the decoded data is `expected`, but the armor carries
+    /// `found`.
+    ChecksumMismatch { expected: u32, found: u32 },
+    /// The body is not valid Base64; offsets are relative to the whole input.
+    InvalidBase64(DecodeError),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ArmorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArmorError::MissingBegin => write!(f, "no armor found"),
+            ArmorError::MissingEnd { line } => {
+                write!(f, "missing END line for armor started on line {}", line)
+            }
+            ArmorError::InvalidLabel { line } => {
+                write!(f, "invalid armor END line on line {}", line)
+            }
+            ArmorError::LabelMismatch { line } => {
+                write!(f, "END line {} does not match the BEGIN line", line)
+            }
+            ArmorError::InvalidHeader { line } => {
+                write!(f, "invalid armor header on line {}", line)
+            }
+            ArmorError::MissingChecksum { line } => {
+                write!(f, "missing checksum before line {}", line)
+            }
+            ArmorError::InvalidChecksum { line } => {
+                write!(f, "invalid checksum line {}", line)
+            }
+            ArmorError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "CRC-24 mismatch, expected {:#08x}, found {:#08x}",
+                expected, found
+            ),
+            ArmorError::InvalidBase64(e) => write!(f, "invalid armor body: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ArmorError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ArmorError::InvalidBase64(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Reasons parsing a `data:` URI can fail.
+///
+/// Offsets are byte offsets into the URI.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DataUriError {
+    /// The URI does not start with `data:`.
+    MissingScheme,
+    /// No `,` separates the media type from the data.
+    MissingComma,
+    /// The media type is not of the form `type/subtype`.
+    InvalidMediaType,
+    /// The parameter at `index` is not an `attribute=value` pair.
+    InvalidParameter { index: usize },
+    /// The `%` at `index` is not followed by two hex digits.
+    InvalidPercentEncoding { index: usize },
+    /// The data is not valid Base64; offsets count from the first byte
+    /// after the comma, once percent escapes are decoded.
+    InvalidBase64(DecodeError),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for DataUriError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataUriError::MissingScheme => write!(f, "missing \"data:\" scheme"),
+            DataUriError::MissingComma => write!(f, "missing comma before the data"),
+            DataUriError::InvalidMediaType => write!(f, "invalid media type"),
+            DataUriError::InvalidParameter { index } => {
+                write!(f, "invalid parameter at offset {}", index)
+            }
+            DataUriError::InvalidPercentEncoding { index } => {
+                write!(f, "invalid percent escape at offset {}", index)
+            }
+            DataUriError::InvalidBase64(e) => write!(f, "invalid data: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for DataUriError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DataUriError::InvalidBase64(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Reasons encoding or decoding a Bech32 string can fail.
+///
+/// Offsets are byte offsets into the string.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Bech32Error {
+    /// The string is longer than the length limit, or too short to hold a
+    /// separator and checksum.
+    InvalidLength { length: usize },
+    /// The string mixes upper and lower case letters.
+    MixedCase,
+    /// No `1` separates the human-readable part from the data.
+    MissingSeparator,
+    /// The human-readable part is empty or longer than 83 characters.
+    InvalidHrp,
+    /// `character` at `index` is outside US-ASCII 33 to 126, or not a data
+    /// symbol.
+    InvalidCharacter { index: usize, character: char },
+    /// The value at `index` of the data to encode does not fit in 5 bits.
+    InvalidData { index: usize },
+    /// The checksum is valid for neither Bech32 nor Bech32m.
+    ///
+    /// `positions` are the offsets of the likely mistyped characters, and
+    /// `candidates` the strings with them corrected; both are empty when no
+    /// one or two substitutions in the data part give a valid checksum. Two
+    /// substitutions are only looked for in strings of at most 90
+    /// characters, and none in data parts longer than 1023 characters,
+    /// where the checksum cannot tell them apart. The candidates are
+    /// suggestions to show the user, not to be used in place of the input.
+    InvalidChecksum {
+        positions: Vec<usize>,
+        candidates: Vec<String>,
+    },
+    /// Regrouped 5-bit data ends in more than 4 bits or in non-zero bits.
+    InvalidPadding,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Bech32Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bech32Error::InvalidLength { length } => write!(f, "invalid length {}", length),
+            Bech32Error::MixedCase => write!(f, "mixed upper and lower case"),
+            Bech32Error::MissingSeparator => write!(f, "missing separator '1'"),
+            Bech32Error::InvalidHrp => write!(f, "invalid human-readable part"),
+            Bech32Error::InvalidCharacter { index, character } => {
+                write!(f, "invalid character {:?} at offset {}", character, index)
+            }
+            Bech32Error::InvalidData { index } => {
+                write!(f, "data value at index {} does not fit in 5 bits", index)
+            }
+            Bech32Error::InvalidChecksum { positions, .. } => {
+                write!(f, "invalid checksum")?;
+                match positions.as_slice() {
+                    [] => Ok(()),
+                    [position] => write!(f, ", likely error at offset {}", position),
+                    [first, second, ..] => {
+                        write!(f, ", likely errors at offsets {} and {}", first, second)
+                    }
+                }
+            }
+            Bech32Error::InvalidPadding => write!(f, "invalid padding in 5-bit data"),
+        }
     }
 }
 
-impl Error for DecodeError {}
+#[cfg(feature = "std")]
+impl error::Error for Bech32Error {}
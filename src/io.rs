@@ -0,0 +1,919 @@
+//! Streaming adapters over [`std::io::Write`] and [`std::io::Read`].
+//!
+//! ```
+//! use std::io::{Read, Write};
+//! use simple_encode::io::{DecoderReader, EncoderWriter};
+//! use simple_encode::Base64;
+//!
+//! let mut writer = EncoderWriter::new(Base64 {}, Vec::new());
+//! writer.write_all(b"Hello, ").unwrap();
+//! writer.write_all(b"world").unwrap();
+//! let encoded = writer.finish().unwrap();
+//! assert_eq!(encoded, b"SGVsbG8sIHdvcmxk");
+//!
+//! let mut reader = DecoderReader::new(Base64 {}, &encoded[..]);
+//! let mut decoded = String::new();
+//! reader.read_to_string(&mut decoded).unwrap();
+//! assert_eq!(decoded, "Hello, world");
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::{
+    Base16, Base32, Base32Engine, Base64, Base64Engine, Base85, Base91, Base91State, DecodeError,
+    DecodeMode,
+};
+
+/// Codecs that can encode and decode their input in pieces, carrying
+/// partial blocks between calls in a [`StreamEncoding::State`].
+pub trait StreamEncoding {
+    /// Input not yet consumed, together with its position in the stream.
+    type State: Default;
+
+    /// Encodes as much of `input` as fills whole blocks into `output` and
+    /// keeps the rest in `state`.
+    fn encode_update(&self, state: &mut Self::State, input: &[u8], output: &mut Vec<u8>);
+    /// Encodes what is left in `state`, including any padding.
+    fn encode_finish(&self, state: &mut Self::State, output: &mut Vec<u8>);
+    /// Decodes the whole groups of symbols in `input` into `output` and keeps
+    /// the rest in `state`.
+    fn decode_update(
+        &self,
+        state: &mut Self::State,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError>;
+    /// Decodes what is left in `state` once the input is exhausted.
+    fn decode_finish(
+        &self,
+        state: &mut Self::State,
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError>;
+}
+
+/// State of a fixed-block codec: an incomplete block and the stream offset.
+#[derive(Debug, Default)]
+pub struct BlockState {
+    pending: [u8; 8],
+    len: usize,
+    /// number of symbols decoded so far, used to place errors in the stream
+    offset: usize,
+    /// whether a padded group has been decoded, ending the data
+    padded: bool,
+}
+
+/// Shape of a fixed-block codec: `block` bytes encode to `group` symbols,
+/// and padded codecs end their last group with `'='`. Lenient decoding
+/// ignores everything after the first padded group, as
+/// [`DecodeMode::Lenient`] does.
+#[derive(Debug, Clone, Copy)]
+struct Blocks {
+    block: usize,
+    group: usize,
+    padded: bool,
+    lenient: bool,
+}
+
+impl BlockState {
+    fn encode_update(
+        &mut self,
+        blocks: Blocks,
+        input: &[u8],
+        output: &mut Vec<u8>,
+        encoded_len: impl Fn(usize) -> usize,
+        encode_block: impl Fn(&[u8], &mut [u8]),
+    ) {
+        let block = blocks.block;
+        let mut input = input;
+        if self.len > 0 {
+            let take = (block - self.len).min(input.len());
+            self.pending[self.len..self.len + take].copy_from_slice(&input[..take]);
+            self.len += take;
+            input = &input[take..];
+            if self.len < block {
+                return;
+            }
+            let start = output.len();
+            output.resize(start + encoded_len(block), 0);
+            encode_block(&self.pending[..block], &mut output[start..]);
+            self.len = 0;
+        }
+
+        let full = input.len() / block * block;
+        let start = output.len();
+        output.resize(start + encoded_len(full), 0);
+        encode_block(&input[..full], &mut output[start..]);
+
+        let rest = &input[full..];
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+    }
+
+    fn encode_finish(
+        &mut self,
+        output: &mut Vec<u8>,
+        encoded_len: impl Fn(usize) -> usize,
+        encode_block: impl Fn(&[u8], &mut [u8]),
+    ) {
+        let start = output.len();
+        output.resize(start + encoded_len(self.len), 0);
+        encode_block(&self.pending[..self.len], &mut output[start..]);
+        *self = BlockState::default();
+    }
+
+    fn decode_update(
+        &mut self,
+        blocks: Blocks,
+        input: &[u8],
+        output: &mut Vec<u8>,
+        decode_group: &impl Fn(&[u8], &mut Vec<u8>) -> Result<(), DecodeError>,
+    ) -> Result<(), DecodeError> {
+        let group = blocks.group;
+        let mut input = input;
+        if self.len > 0 {
+            let take = (group - self.len).min(input.len());
+            self.pending[self.len..self.len + take].copy_from_slice(&input[..take]);
+            self.len += take;
+            input = &input[take..];
+            if self.len < group {
+                return Ok(());
+            }
+            let pending = self.pending;
+            self.decode_groups(blocks, &pending[..group], output, decode_group)?;
+            self.len = 0;
+        }
+
+        let full = input.len() / group * group;
+        self.decode_groups(blocks, &input[..full], output, decode_group)?;
+
+        let rest = &input[full..];
+        if self.padded && !rest.is_empty() {
+            if blocks.lenient {
+                return Ok(());
+            }
+            return Err(DecodeError::InvalidPadding { index: self.offset });
+        }
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+        Ok(())
+    }
+
+    fn decode_finish(
+        &mut self,
+        blocks: Blocks,
+        output: &mut Vec<u8>,
+        decode_group: &impl Fn(&[u8], &mut Vec<u8>) -> Result<(), DecodeError>,
+    ) -> Result<(), DecodeError> {
+        let pending = self.pending;
+        let result = self.decode_groups(blocks, &pending[..self.len], output, decode_group);
+        *self = BlockState::default();
+        result
+    }
+
+    /// Decodes whole groups, stopping after the first group that holds
+    /// padding; anything following it is an error unless decoding is
+    /// lenient.
+    fn decode_groups(
+        &mut self,
+        blocks: Blocks,
+        groups: &[u8],
+        output: &mut Vec<u8>,
+        decode_group: &impl Fn(&[u8], &mut Vec<u8>) -> Result<(), DecodeError>,
+    ) -> Result<(), DecodeError> {
+        if groups.is_empty() {
+            return Ok(());
+        }
+        if self.padded {
+            if blocks.lenient {
+                return Ok(());
+            }
+            return Err(DecodeError::InvalidPadding { index: self.offset });
+        }
+
+        let padding = match blocks.padded {
+            true => groups.iter().position(|&byte| byte == b'='),
+            false => None,
+        };
+        let end = match padding {
+            Some(pad) => {
+                self.padded = true;
+                // the padded group runs to the end of its block
+                ((pad / blocks.group + 1) * blocks.group).min(groups.len())
+            }
+            None => groups.len(),
+        };
+
+        decode_group(&groups[..end], output).map_err(|e| e.offset_by(self.offset))?;
+        self.offset += end;
+        if end < groups.len() && !blocks.lenient {
+            return Err(DecodeError::InvalidPadding { index: self.offset });
+        }
+        Ok(())
+    }
+}
+
+/// Decodes `input` through a `decode_to_slice` function, appending to
+/// `output`.
+fn decode_with(
+    input: &[u8],
+    output: &mut Vec<u8>,
+    decoded_len_estimate: impl Fn(usize) -> usize,
+    decode_to_slice: impl Fn(&str, &mut [u8]) -> Result<usize, DecodeError>,
+) -> Result<(), DecodeError> {
+    let input = std::str::from_utf8(input)
+        .map_err(|e| DecodeError::invalid_byte(input, e.valid_up_to()))?;
+    let start = output.len();
+    output.resize(start + decoded_len_estimate(input.len()), 0);
+    let len = decode_to_slice(input, &mut output[start..])?;
+    output.truncate(start + len);
+    Ok(())
+}
+
+macro_rules! block_stream_encoding {
+    ($codec:ident, $block:expr, $group:expr, $padded:expr) => {
+        impl StreamEncoding for $codec {
+            type State = BlockState;
+
+            fn encode_update(&self, state: &mut BlockState, input: &[u8], output: &mut Vec<u8>) {
+                state.encode_update(
+                    Blocks {
+                        block: $block,
+                        group: $group,
+                        padded: $padded,
+                        lenient: false,
+                    },
+                    input,
+                    output,
+                    $codec::encoded_len,
+                    $codec::encode_block,
+                );
+            }
+            fn encode_finish(&self, state: &mut BlockState, output: &mut Vec<u8>) {
+                state.encode_finish(output, $codec::encoded_len, $codec::encode_block);
+            }
+            fn decode_update(
+                &self,
+                state: &mut BlockState,
+                input: &[u8],
+                output: &mut Vec<u8>,
+            ) -> Result<(), DecodeError> {
+                state.decode_update(
+                    Blocks {
+                        block: $block,
+                        group: $group,
+                        padded: $padded,
+                        lenient: false,
+                    },
+                    input,
+                    output,
+                    &|groups, output| {
+                        decode_with(
+                            groups,
+                            output,
+                            $codec::decoded_len_estimate,
+                            $codec::decode_to_slice,
+                        )
+                    },
+                )
+            }
+            fn decode_finish(
+                &self,
+                state: &mut BlockState,
+                output: &mut Vec<u8>,
+            ) -> Result<(), DecodeError> {
+                state.decode_finish(
+                    Blocks {
+                        block: $block,
+                        group: $group,
+                        padded: $padded,
+                        lenient: false,
+                    },
+                    output,
+                    &|groups, output| {
+                        decode_with(
+                            groups,
+                            output,
+                            $codec::decoded_len_estimate,
+                            $codec::decode_to_slice,
+                        )
+                    },
+                )
+            }
+        }
+    };
+}
+
+block_stream_encoding!(Base16, 1, 2, false);
+block_stream_encoding!(Base85, 4, 5, false);
+
+const BASE32_BLOCKS: Blocks = Blocks {
+    block: 5,
+    group: 8,
+    padded: true,
+    lenient: false,
+};
+
+impl StreamEncoding for Base32Engine {
+    type State = BlockState;
+
+    fn encode_update(&self, state: &mut BlockState, input: &[u8], output: &mut Vec<u8>) {
+        state.encode_update(
+            BASE32_BLOCKS,
+            input,
+            output,
+            |len| self.encoded_len(len),
+            |input, output| self.encode_block(input, output),
+        );
+    }
+    fn encode_finish(&self, state: &mut BlockState, output: &mut Vec<u8>) {
+        state.encode_finish(
+            output,
+            |len| self.encoded_len(len),
+            |input, output| self.encode_block(input, output),
+        );
+    }
+    fn decode_update(
+        &self,
+        state: &mut BlockState,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        state.decode_update(BASE32_BLOCKS, input, output, &|groups, output| {
+            decode_with(
+                groups,
+                output,
+                |len| self.decoded_len_estimate(len),
+                |input, output| self.decode_to_slice(input, output),
+            )
+        })
+    }
+    fn decode_finish(
+        &self,
+        state: &mut BlockState,
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        state.decode_finish(BASE32_BLOCKS, output, &|groups, output| {
+            decode_with(
+                groups,
+                output,
+                |len| self.decoded_len_estimate(len),
+                |input, output| self.decode_to_slice(input, output),
+            )
+        })
+    }
+}
+
+impl StreamEncoding for Base32 {
+    type State = BlockState;
+
+    fn encode_update(&self, state: &mut BlockState, input: &[u8], output: &mut Vec<u8>) {
+        Base32Engine::STANDARD.encode_update(state, input, output)
+    }
+    fn encode_finish(&self, state: &mut BlockState, output: &mut Vec<u8>) {
+        Base32Engine::STANDARD.encode_finish(state, output)
+    }
+    fn decode_update(
+        &self,
+        state: &mut BlockState,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        Base32Engine::STANDARD.decode_update(state, input, output)
+    }
+    fn decode_finish(
+        &self,
+        state: &mut BlockState,
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        Base32Engine::STANDARD.decode_finish(state, output)
+    }
+}
+
+const BASE64_BLOCKS: Blocks = Blocks {
+    block: 3,
+    group: 4,
+    padded: true,
+    lenient: false,
+};
+
+/// [`BASE64_BLOCKS`], decoded leniently if `engine` is.
+fn base64_blocks(engine: &Base64Engine) -> Blocks {
+    Blocks {
+        lenient: engine.decode_mode == DecodeMode::Lenient,
+        ..BASE64_BLOCKS
+    }
+}
+
+/// State of a Base64 stream: the incomplete block and, for wrapped output,
+/// the column of the current line.
+///
+/// Characters the engine skips are dropped before decoding, so error
+/// offsets count only the symbols and padding of the stream.
+#[derive(Debug, Default)]
+pub struct Base64StreamState {
+    block: BlockState,
+    column: usize,
+}
+
+impl Base64StreamState {
+    /// Moves the symbols written to `output` after `start` onto lines, with
+    /// the separator written before the first symbol of each new line.
+    fn wrap(&mut self, engine: &Base64Engine, output: &mut Vec<u8>, start: usize) {
+        let Some((width, ending)) = engine.line_wrap else {
+            return;
+        };
+        let symbols = output.split_off(start);
+        for &symbol in &symbols {
+            if self.column == width {
+                output.extend_from_slice(ending.as_str().as_bytes());
+                self.column = 0;
+            }
+            output.push(symbol);
+            self.column += 1;
+        }
+    }
+}
+
+impl StreamEncoding for Base64Engine {
+    type State = Base64StreamState;
+
+    fn encode_update(&self, state: &mut Base64StreamState, input: &[u8], output: &mut Vec<u8>) {
+        let start = output.len();
+        state.block.encode_update(
+            BASE64_BLOCKS,
+            input,
+            output,
+            |len| self.unwrapped_len(len),
+            |input, output| self.encode_block(input, output),
+        );
+        state.wrap(self, output, start);
+    }
+    fn encode_finish(&self, state: &mut Base64StreamState, output: &mut Vec<u8>) {
+        let start = output.len();
+        state.block.encode_finish(
+            output,
+            |len| self.unwrapped_len(len),
+            |input, output| self.encode_block(input, output),
+        );
+        state.wrap(self, output, start);
+        state.column = 0;
+    }
+    fn decode_update(
+        &self,
+        state: &mut Base64StreamState,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        let kept: Vec<u8>;
+        let input = if input.iter().any(|&byte| self.skips(byte)) {
+            kept = input
+                .iter()
+                .copied()
+                .filter(|&byte| !self.skips(byte))
+                .collect();
+            &kept[..]
+        } else {
+            input
+        };
+        state
+            .block
+            .decode_update(base64_blocks(self), input, output, &|groups, output| {
+                decode_with(
+                    groups,
+                    output,
+                    |len| self.decoded_len_estimate(len),
+                    |input, output| self.decode_to_slice(input, output),
+                )
+            })
+    }
+    fn decode_finish(
+        &self,
+        state: &mut Base64StreamState,
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        state
+            .block
+            .decode_finish(base64_blocks(self), output, &|groups, output| {
+                decode_with(
+                    groups,
+                    output,
+                    |len| self.decoded_len_estimate(len),
+                    |input, output| self.decode_to_slice(input, output),
+                )
+            })
+    }
+}
+
+impl StreamEncoding for Base64 {
+    type State = Base64StreamState;
+
+    fn encode_update(&self, state: &mut Base64StreamState, input: &[u8], output: &mut Vec<u8>) {
+        Base64Engine::STANDARD.encode_update(state, input, output)
+    }
+    fn encode_finish(&self, state: &mut Base64StreamState, output: &mut Vec<u8>) {
+        Base64Engine::STANDARD.encode_finish(state, output)
+    }
+    fn decode_update(
+        &self,
+        state: &mut Base64StreamState,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        Base64Engine::STANDARD.decode_update(state, input, output)
+    }
+    fn decode_finish(
+        &self,
+        state: &mut Base64StreamState,
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        Base64Engine::STANDARD.decode_finish(state, output)
+    }
+}
+
+/// State of the Base91 bit queue and the stream offset.
+#[derive(Debug, Default)]
+pub struct Base91StreamState {
+    state: Base91State,
+    offset: usize,
+}
+
+impl StreamEncoding for Base91 {
+    type State = Base91StreamState;
+
+    fn encode_update(&self, state: &mut Base91StreamState, input: &[u8], output: &mut Vec<u8>) {
+        state.state.encode(input, |symbol| output.push(symbol));
+    }
+    fn encode_finish(&self, state: &mut Base91StreamState, output: &mut Vec<u8>) {
+        state.state.encode_finish(|symbol| output.push(symbol));
+    }
+    fn decode_update(
+        &self,
+        state: &mut Base91StreamState,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        state
+            .state
+            .decode(input, state.offset, |byte| output.push(byte))?;
+        state.offset += input.len();
+        Ok(())
+    }
+    fn decode_finish(
+        &self,
+        state: &mut Base91StreamState,
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        state.state.decode_finish(|byte| output.push(byte));
+        Ok(())
+    }
+}
+
+/// Encodes everything written to it into the wrapped writer.
+///
+/// Incomplete blocks are held back until more input arrives; call
+/// [`EncoderWriter::finish`] to write them out with their padding. Dropping
+/// the writer finishes it too, but any error is lost.
+///
+/// Symbols the wrapped writer fails to take are kept and written before
+/// anything else, so a failed call can be retried without losing or
+/// repeating output.
+#[derive(Debug)]
+pub struct EncoderWriter<E: StreamEncoding, W: Write> {
+    codec: E,
+    state: E::State,
+    /// symbols encoded but not yet taken by `inner`
+    output: Vec<u8>,
+    inner: Option<W>,
+    /// whether the final block has been encoded
+    finished: bool,
+}
+
+impl<E: StreamEncoding, W: Write> EncoderWriter<E, W> {
+    pub fn new(codec: E, inner: W) -> EncoderWriter<E, W> {
+        EncoderWriter {
+            codec,
+            state: E::State::default(),
+            output: Vec::new(),
+            inner: Some(inner),
+            finished: false,
+        }
+    }
+
+    /// Writes the final partial block and padding and returns the wrapped
+    /// writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_final()?;
+        Ok(self.inner.take().expect("writer is only taken on finish"))
+    }
+
+    fn write_final(&mut self) -> io::Result<()> {
+        self.finished = true;
+        self.codec.encode_finish(&mut self.state, &mut self.output);
+        self.write_output()?;
+        self.inner
+            .as_mut()
+            .expect("writer is only taken on finish")
+            .flush()
+    }
+
+    /// Writes the pending symbols, dropping each piece the wrapped writer
+    /// takes, so an error leaves only the rest pending.
+    fn write_output(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().expect("writer is only taken on finish");
+        while !self.output.is_empty() {
+            match inner.write(&self.output) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => {
+                    self.output.drain(..n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: StreamEncoding, W: Write> Write for EncoderWriter<E, W> {
+    /// Writes the symbols left over from an earlier call, then encodes
+    /// `buf`. Once encoded, `buf` counts as written even if the wrapped
+    /// writer does not take its symbols yet; they are kept for the next
+    /// call, which reports the error if it persists.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_output()?;
+        self.codec
+            .encode_update(&mut self.state, buf, &mut self.output);
+        let _ = self.write_output();
+        Ok(buf.len())
+    }
+
+    /// Writes any pending symbols and flushes the wrapped writer. Symbols
+    /// of an incomplete block are only written by [`EncoderWriter::finish`].
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_output()?;
+        self.inner
+            .as_mut()
+            .expect("writer is only taken on finish")
+            .flush()
+    }
+}
+
+impl<E: StreamEncoding, W: Write> Drop for EncoderWriter<E, W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() && !self.finished {
+            let _ = self.write_final();
+        }
+    }
+}
+
+/// Decodes the symbols read from the wrapped reader.
+///
+/// Invalid input is reported as an [`io::ErrorKind::InvalidData`] error
+/// wrapping the [`DecodeError`], whose offsets count from the start of the
+/// stream.
+#[derive(Debug)]
+pub struct DecoderReader<E: StreamEncoding, R: Read> {
+    codec: E,
+    state: E::State,
+    inner: R,
+    decoded: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<E: StreamEncoding, R: Read> DecoderReader<E, R> {
+    pub fn new(codec: E, inner: R) -> DecoderReader<E, R> {
+        DecoderReader {
+            codec,
+            state: E::State::default(),
+            inner,
+            decoded: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the wrapped reader, dropping any decoded bytes not read yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        let mut buffer = [0u8; 4096];
+        self.decoded.clear();
+        self.position = 0;
+        while self.decoded.is_empty() && !self.finished {
+            let n = match self.inner.read(&mut buffer) {
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            let result = if n == 0 {
+                self.finished = true;
+                self.codec.decode_finish(&mut self.state, &mut self.decoded)
+            } else {
+                self.codec
+                    .decode_update(&mut self.state, &buffer[..n], &mut self.decoded)
+            };
+            result.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: StreamEncoding, R: Read> Read for DecoderReader<E, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.decoded.len() {
+            self.fill()?;
+        }
+        let available = &self.decoded[self.position..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Base16, Base32, Base64, Base85, Base91, Encoding};
+
+    /// Writes `data` in pieces of `step` bytes and reads it back the same way.
+    fn round_trip<E>(codec: E, data: &[u8], step: usize)
+    where
+        E: StreamEncoding + Encoding + Copy,
+    {
+        let mut writer = EncoderWriter::new(codec, Vec::new());
+        for chunk in data.chunks(step) {
+            writer.write_all(chunk).unwrap();
+        }
+        let encoded = writer.finish().unwrap();
+        assert_eq!(encoded, codec.encode(data).as_bytes(), "{}", codec.name());
+
+        let mut reader = DecoderReader::new(codec, &encoded[..]);
+        let mut decoded = Vec::new();
+        let mut buffer = vec![0u8; step];
+        loop {
+            let n = reader.read(&mut buffer).unwrap();
+            if n == 0 {
+                break;
+            }
+            decoded.extend_from_slice(&buffer[..n]);
+        }
+        assert_eq!(decoded, data, "{}", codec.name());
+    }
+
+    #[test]
+    fn stream_round_trip() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i * 13 + i / 255) as u8).collect();
+        for len in [0, 1, 2, 3, 4, 5, 7, 100, 10_000] {
+            for step in [1, 2, 3, 7, 64, 5000] {
+                round_trip(Base16 {}, &data[..len], step);
+                round_trip(Base32 {}, &data[..len], step);
+                round_trip(Base64 {}, &data[..len], step);
+                round_trip(Base85 {}, &data[..len], step);
+                round_trip(Base91 {}, &data[..len], step);
+                round_trip(Base64Engine::URL_SAFE_NO_PAD, &data[..len], step);
+                round_trip(Base64Engine::MIME, &data[..len], step);
+                round_trip(Base32Engine::HEX_NO_PAD, &data[..len], step);
+            }
+        }
+    }
+
+    #[test]
+    fn stream_errors() {
+        let mut decoded = Vec::new();
+        let err = DecoderReader::new(Base64 {}, &b"SGVsbG8s!HdvcmxK"[..])
+            .read_to_end(&mut decoded)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let inner = err.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+        assert_eq!(
+            *inner,
+            DecodeError::InvalidCharacter {
+                index: 8,
+                character: '!'
+            }
+        );
+
+        let err = DecoderReader::new(Base64 {}, &b"SGk=SGk="[..])
+            .read_to_end(&mut decoded)
+            .unwrap_err();
+        let inner = err.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+        assert_eq!(*inner, DecodeError::InvalidPadding { index: 4 });
+    }
+
+    /// Source that hands out at most `step` bytes per read.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn stream_decode_modes() {
+        let lenient = Base64Engine::STANDARD.with_decode_mode(DecodeMode::Lenient);
+        for engine in [Base64Engine::STANDARD, lenient] {
+            for input in [
+                "SGk=SGk=",
+                "SGk=",
+                "SGVsbG8=IQ==",
+                "SGl=",
+                "SG==!",
+                "S===",
+                "SGk",
+            ] {
+                for step in [1, 3, 4096] {
+                    let mut reader = DecoderReader::new(
+                        engine,
+                        Trickle {
+                            data: input.as_bytes(),
+                            step,
+                        },
+                    );
+                    let mut decoded = Vec::new();
+                    let streamed = reader.read_to_end(&mut decoded).map(|_| decoded);
+                    match engine.decode(input) {
+                        Ok(data) => assert_eq!(streamed.unwrap(), data, "{}", input),
+                        Err(_) => assert!(streamed.is_err(), "{}", input),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sink that fails the first `failures` writes and records the rest.
+    struct Flaky {
+        failures: usize,
+        written: Vec<u8>,
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(io::Error::other("flaky"));
+            }
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_errors() {
+        // the symbols of "abc" are kept when the sink fails, not encoded twice
+        let mut sink = Flaky {
+            failures: 1,
+            written: Vec::new(),
+        };
+        let mut writer = EncoderWriter::new(Base64 {}, &mut sink);
+        writer.write_all(b"abc").unwrap();
+        writer.write_all(b"de").unwrap();
+        writer.finish().unwrap();
+        assert_eq!(sink.written, b"YWJjZGU=");
+
+        // a pending error is reported before any new input is taken
+        let mut sink = Flaky {
+            failures: 2,
+            written: Vec::new(),
+        };
+        let mut writer = EncoderWriter::new(Base64 {}, &mut sink);
+        assert_eq!(writer.write(b"abc").unwrap(), 3);
+        assert!(writer.write(b"def").is_err());
+        writer.write_all(b"def").unwrap();
+        writer.finish().unwrap();
+        assert_eq!(sink.written, b"YWJjZGVm");
+
+        // a failed finish is not repeated on drop
+        let mut sink = Flaky {
+            failures: 1,
+            written: Vec::new(),
+        };
+        let mut writer = EncoderWriter::new(Base64 {}, &mut sink);
+        writer.write_all(b"ab").unwrap();
+        assert!(writer.finish().is_err());
+        assert_eq!(sink.written, b"");
+    }
+
+    #[test]
+    fn drop_finishes() {
+        let mut encoded = Vec::new();
+        {
+            let mut writer = EncoderWriter::new(Base32 {}, &mut encoded);
+            writer.write_all(b"Hi").unwrap();
+        }
+        assert_eq!(encoded, b"JBUQ====");
+    }
+}
@@ -1,8 +1,69 @@
-use anyhow::Result;
-use errors::DecodeError;
-
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use bits::Regroup;
+
+mod alphabet;
+#[cfg(feature = "alloc")]
+pub mod armor;
+#[cfg(feature = "alloc")]
+pub mod bech32;
+mod bits;
+#[cfg(feature = "alloc")]
+pub mod data_uri;
 pub mod errors;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "alloc")]
+pub mod pem;
+#[cfg(feature = "alloc")]
+mod sha256;
+
+pub use alphabet::Alphabet;
+#[cfg(feature = "alloc")]
+pub use errors::{ArmorError, Bech32Error, DataUriError, PemError};
+pub use errors::{DecodeError, EncodeError, Error};
+
+/// Result type used throughout the crate, defaulting to [`Error`].
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// Common interface shared by every codec in this crate.
+///
+/// The codec structs are zero sized, so a generic function can take any of
+/// them by value or through `&dyn Encoding`:
+///
+/// ```
+/// use simple_encode::{Base32, Base64, Encoding};
+///
+/// fn store(codec: &dyn Encoding, data: &[u8]) -> String {
+///     format!("{}:{}", codec.name(), codec.encode(data))
+/// }
+///
+/// assert_eq!(store(&Base64 {}, b"Hi"), "base64:SGk=");
+/// assert_eq!(store(&Base32 {}, b"Hi"), "base32:JBUQ====");
+/// ```
+#[cfg(feature = "alloc")]
+pub trait Encoding {
+    /// Short lowercase name of the encoding, e.g. `"base64"`.
+    fn name(&self) -> &'static str;
+    /// Symbols of the encoding ordered by their value, without padding.
+    fn alphabet(&self) -> &'static str;
+    /// Whether the encoded output is padded with `=` to a full block.
+    fn is_padded(&self) -> bool;
+    /// Exact encoded length for `input_len` bytes, or `None` when it depends
+    /// on the content of the input.
+    fn encoded_len(&self, input_len: usize) -> Option<usize>;
+    fn encode(&self, input: &[u8]) -> String;
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError>;
+}
+
+static BASE16_ALPHABET: Alphabet<16> = Alphabet::new("0123456789abcdef").ignore_case();
 
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Base16 {}
 
 impl Base16 {
@@ -10,654 +71,2876 @@ impl Base16 {
     ///
     /// # Example
     /// ```
-    /// use simple_encode:Base16;
+    /// use simple_encode::Base16;
     ///
     /// fn main() {
     ///     let input = b"Test";
-    ///     let enc = Base16::encode(input).unwarp();
+    ///     let enc = Base16::encode(input);
     ///     println!("{}", enc);
     /// }
     /// ```
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let encoded = input.iter().map(|byte| format!("{:02x}", byte)).collect();
-        Ok(encoded)
+    #[cfg(feature = "alloc")]
+    pub fn encode(input: &[u8]) -> String {
+        let mut encoded = String::with_capacity(Base16::encoded_len(input.len()));
+        Base16::encode_append(input, &mut encoded);
+        encoded
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        if input.len() % 2 != 0 {
-            Err(DecodeError::new("hex string has an odd length").into())
-        } else {
-            let mut ret = Vec::new();
-            for i in (0..input.len()).step_by(2) {
-                let value = u8::from_str_radix(&input[i..i + 2], 16)?;
-                // println!("{} - {}", &input[i..i + 2], value);
-                ret.push(value);
+    /// Appends the encoding of `input` to `output`, so one `String` can be
+    /// reused across calls.
+    #[cfg(feature = "alloc")]
+    pub fn encode_append(input: &[u8], output: &mut String) {
+        append_blocks(input, output, Base16::encoded_len, Base16::encode_block);
+    }
+    #[cfg(feature = "alloc")]
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = vec![0u8; Base16::decoded_len_estimate(input.len())];
+        let len = Base16::decode_to_slice(input, &mut decoded)?;
+        decoded.truncate(len);
+        Ok(decoded)
+    }
+    /// Length of the encoded output for `input_len` bytes.
+    pub const fn encoded_len(input_len: usize) -> usize {
+        input_len * 2
+    }
+    /// Upper bound of the decoded length for an input of `input_len` symbols.
+    pub const fn decoded_len_estimate(input_len: usize) -> usize {
+        input_len / 2
+    }
+    /// Encodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+        let len = Base16::encoded_len(input.len());
+        let output = output
+            .get_mut(..len)
+            .ok_or(EncodeError::BufferTooSmall { needed: len })?;
+        Base16::encode_block(input, output);
+        Ok(len)
+    }
+    /// Decodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let needed = input.len() / 2;
+        if output.len() < needed {
+            return Err(DecodeError::BufferTooSmall { needed });
+        }
+
+        let mut written = 0;
+        let mut high = None;
+        for (index, byte) in input.bytes().enumerate() {
+            let value = match BASE16_ALPHABET.value(byte) {
+                Some(v) => v,
+                None => return Err(DecodeError::invalid_character(input, index)),
+            };
+            match high.take() {
+                Some(h) => {
+                    output[written] = (h << 4) | value;
+                    written += 1;
+                }
+                None => high = Some(value),
             }
-            Ok(ret)
+        }
+
+        if high.is_some() {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
+        }
+        Ok(written)
+    }
+    /// Writes the encoding of `input` into `output`, which must be exactly
+    /// `encoded_len(input.len())` long.
+    fn encode_block(input: &[u8], output: &mut [u8]) {
+        for (byte, pair) in input.iter().zip(output.chunks_exact_mut(2)) {
+            pair[0] = BASE16_ALPHABET.symbol((byte >> 4) as usize);
+            pair[1] = BASE16_ALPHABET.symbol((byte & 0x0f) as usize);
         }
     }
 }
 
-const BASE32_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
-
-pub struct Base32 {}
+#[cfg(feature = "alloc")]
+impl Encoding for Base16 {
+    fn name(&self) -> &'static str {
+        "base16"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE16_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some(Base16::encoded_len(input_len))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base16::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base16::decode(input)
+    }
+}
 
-impl Base32 {
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let mut encoded = String::new();
-        let mut buffer = 0u32;
-        let mut bits_left = 0;
+static BASE32_ALPHABET: Alphabet<32> = Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567");
+static BASE32_HEX_ALPHABET: Alphabet<32> = Alphabet::new("0123456789ABCDEFGHIJKLMNOPQRSTUV");
+
+/// How a decoder treats `=` padding at the end of its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodePadding {
+    /// Input that does not end on a whole block must be padded.
+    Required,
+    /// Padding may be present or not.
+    Optional,
+    /// Padding is rejected.
+    Forbidden,
+}
 
-        for &byte in input {
-            buffer = (buffer << 8) | (byte as u32);
-            bits_left += 8;
+/// Base32 codec with a configurable alphabet, padding and letter case.
+///
+/// The associated constants cover RFC 4648 §6 (`STANDARD`) and the "extended
+/// hex" alphabet of §7 (`HEX`), whose encoding sorts like its input, each with
+/// and without padding:
+///
+/// ```
+/// use simple_encode::Base32Engine;
+///
+/// let secret = Base32Engine::STANDARD_NO_PAD.encode(b"Hello!");
+/// assert_eq!(secret, "JBSWY3DPEE");
+/// assert_eq!(Base32Engine::HEX_NO_PAD.encode(b"Hello!"), "91IMOR3F44");
+///
+/// let lowercase = Base32Engine::STANDARD_NO_PAD.with_ignore_case(true);
+/// assert_eq!(lowercase.decode("jbswy3dpee").unwrap(), b"Hello!");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Base32Engine {
+    alphabet: &'static Alphabet<32>,
+    encode_padding: bool,
+    decode_padding: DecodePadding,
+    ignore_case: bool,
+}
 
-            while bits_left >= 5 {
-                bits_left -= 5;
-                let index = (buffer >> bits_left) & 0b11111;
-                let value = match BASE32_ALPHABET.chars().nth(index as usize) {
-                    Some(v) => v,
-                    None => {
-                        return Err(DecodeError::new(&format!(
-                            "get value from alphabet failed, index {}, max length {}",
-                            index,
-                            BASE32_ALPHABET.len(),
-                        ))
-                        .into())
-                    }
-                };
-                encoded.push(value);
-            }
+impl Base32Engine {
+    /// Standard alphabet, padded output, padding required on decode.
+    pub const STANDARD: Base32Engine = Base32Engine::new(&BASE32_ALPHABET);
+    /// Standard alphabet without padding, as TOTP secrets are usually shared.
+    pub const STANDARD_NO_PAD: Base32Engine = Base32Engine::new(&BASE32_ALPHABET)
+        .with_encode_padding(false)
+        .with_decode_padding(DecodePadding::Forbidden);
+    /// Extended hex alphabet (`0-9A-V`), padded output, padding required on
+    /// decode.
+    pub const HEX: Base32Engine = Base32Engine::new(&BASE32_HEX_ALPHABET);
+    /// Extended hex alphabet without padding, as used by DNSSEC NSEC3.
+    pub const HEX_NO_PAD: Base32Engine = Base32Engine::new(&BASE32_HEX_ALPHABET)
+        .with_encode_padding(false)
+        .with_decode_padding(DecodePadding::Forbidden);
+
+    /// Engine for `alphabet` that pads its output and requires padding on
+    /// decode.
+    pub const fn new(alphabet: &'static Alphabet<32>) -> Base32Engine {
+        Base32Engine {
+            alphabet,
+            encode_padding: true,
+            decode_padding: DecodePadding::Required,
+            ignore_case: false,
+        }
+    }
+    /// Whether `encode` pads its output with `=` to a multiple of 8.
+    pub const fn with_encode_padding(mut self, padding: bool) -> Base32Engine {
+        self.encode_padding = padding;
+        self
+    }
+    /// How `decode` treats missing or present padding.
+    pub const fn with_decode_padding(mut self, padding: DecodePadding) -> Base32Engine {
+        self.decode_padding = padding;
+        self
+    }
+    /// Whether `decode` also accepts the other case of every letter of the
+    /// alphabet.
+    pub const fn with_ignore_case(mut self, ignore_case: bool) -> Base32Engine {
+        self.ignore_case = ignore_case;
+        self
+    }
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, input: &[u8]) -> String {
+        let mut encoded = String::with_capacity(self.encoded_len(input.len()));
+        self.encode_append(input, &mut encoded);
+        encoded
+    }
+    /// Appends the encoding of `input` to `output`, so one `String` can be
+    /// reused across calls.
+    #[cfg(feature = "alloc")]
+    pub fn encode_append(&self, input: &[u8], output: &mut String) {
+        append_blocks(
+            input,
+            output,
+            |len| self.encoded_len(len),
+            |input, output| self.encode_block(input, output),
+        );
+    }
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = vec![0u8; Base32Engine::decoded_len(input)];
+        let len = self.decode_to_slice(input, &mut decoded)?;
+        decoded.truncate(len);
+        Ok(decoded)
+    }
+    /// Length of the encoded output for `input_len` bytes.
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        if self.encode_padding {
+            input_len.div_ceil(5) * 8
+        } else {
+            (input_len * 8).div_ceil(5)
+        }
+    }
+    /// Upper bound of the decoded length for an input of `input_len` symbols.
+    pub const fn decoded_len_estimate(&self, input_len: usize) -> usize {
+        input_len / 8 * 5 + input_len % 8 * 5 / 8
+    }
+    /// Encodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn encode_to_slice(&self, input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+        let len = self.encoded_len(input.len());
+        let output = output
+            .get_mut(..len)
+            .ok_or(EncodeError::BufferTooSmall { needed: len })?;
+        self.encode_block(input, output);
+        Ok(len)
+    }
+    /// Decodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn decode_to_slice(&self, input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let needed = Base32Engine::decoded_len(input);
+        if output.len() < needed {
+            return Err(DecodeError::BufferTooSmall { needed });
         }
 
-        if bits_left > 0 {
-            let index = (buffer << (5 - bits_left)) & 0b11111;
-            let value = match BASE32_ALPHABET.chars().nth(index as usize) {
+        let mut written = 0;
+        let mut bits = Regroup::<5, 8>::new();
+        let mut symbols = 0usize;
+        let mut last_symbol = 0;
+        let mut padding = 0;
+        let mut padding_at = None;
+
+        for (i, &byte) in input.as_bytes().iter().enumerate() {
+            let index = match self.value(byte) {
                 Some(v) => v,
-                None => {
-                    return Err(DecodeError::new(&format!(
-                        "get value from alphabet failed, index {}, max length {}",
-                        index,
-                        BASE32_ALPHABET.len(),
-                    ))
-                    .into())
+                None if byte == b'=' => {
+                    padding_at.get_or_insert(i);
+                    padding += 1;
+                    continue;
                 }
+                None => return Err(DecodeError::invalid_character(input, i)),
             };
-            encoded.push(value);
+            if padding > 0 {
+                return Err(DecodeError::InvalidPadding { index: i });
+            }
+            bits.push(index, |byte| {
+                output[written] = byte;
+                written += 1;
+            });
+            symbols += 1;
+            last_symbol = i;
         }
 
-        while encoded.len() % 8 != 0 {
-            encoded.push('=');
+        // a trailing group of n bytes is written as 2, 4, 5 or 7 symbols
+        if matches!(symbols % 8, 1 | 3 | 6) {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
+        }
+        if let Some(index) = padding_at {
+            if padding != (8 - symbols % 8) % 8 {
+                return Err(DecodeError::InvalidPadding { index });
+            }
+        }
+        if bits.has_trailing_bits() {
+            return Err(DecodeError::InvalidTrailingBits { index: last_symbol });
         }
 
-        Ok(encoded)
+        match (self.decode_padding, padding_at) {
+            (DecodePadding::Forbidden, Some(index)) => Err(DecodeError::InvalidPadding { index }),
+            (DecodePadding::Required, None) if !symbols.is_multiple_of(8) => {
+                Err(DecodeError::InvalidPadding { index: input.len() })
+            }
+            _ => Ok(written),
+        }
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        let mut decoded = Vec::new();
-        let mut buffer = 0u32;
-        let mut bits_left = 0;
-
-        for c in input.chars() {
-            if c == '=' {
-                break;
+    /// Value of the symbol `byte`, trying the other letter case too when the
+    /// engine ignores case.
+    fn value(&self, byte: u8) -> Option<u8> {
+        match self.alphabet.value(byte) {
+            None if self.ignore_case && byte.is_ascii_lowercase() => {
+                self.alphabet.value(byte.to_ascii_uppercase())
             }
+            None if self.ignore_case => self.alphabet.value(byte.to_ascii_lowercase()),
+            value => value,
+        }
+    }
+    /// Number of bytes `decode_to_slice` writes for `input`, counting the
+    /// symbols up to the first `'='`.
+    fn decoded_len(input: &str) -> usize {
+        let symbols = input.find('=').unwrap_or(input.len());
+        symbols * 5 / 8
+    }
+    /// Writes the encoding of `input` into `output`, which must be exactly
+    /// `encoded_len(input.len())` long.
+    fn encode_block(&self, input: &[u8], output: &mut [u8]) {
+        let mut written = 0;
+        let mut bits = Regroup::<8, 5>::new();
+        let mut emit = |index: u8| {
+            output[written] = self.alphabet.symbol(index as usize);
+            written += 1;
+        };
 
-            let index = match BASE32_ALPHABET.find(c) {
-                Some(i) => i,
-                None => return Err(DecodeError::new("invalid base32 character").into()),
-            };
-            buffer = (buffer << 5) | (index as u32);
-            bits_left += 5;
+        for &byte in input {
+            bits.push(byte, &mut emit);
+        }
+        bits.flush(&mut emit);
 
-            if bits_left >= 8 {
-                bits_left -= 8;
-                decoded.push((buffer >> bits_left) as u8);
-            }
+        for pad in &mut output[written..] {
+            *pad = b'=';
         }
+    }
+}
 
-        Ok(decoded)
+#[cfg(feature = "alloc")]
+impl Encoding for Base32Engine {
+    fn name(&self) -> &'static str {
+        if core::ptr::eq(self.alphabet, &BASE32_HEX_ALPHABET) {
+            "base32hex"
+        } else {
+            "base32"
+        }
+    }
+    fn alphabet(&self) -> &'static str {
+        self.alphabet.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        self.encode_padding
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some(Base32Engine::encoded_len(self, input_len))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base32Engine::encode(self, input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base32Engine::decode(self, input)
     }
 }
 
-const BASE36_ALPHABET: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+/// Standard padded Base32 of RFC 4648, the [`Base32Engine::STANDARD`] preset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base32 {}
 
-pub struct Base36 {}
+impl Base32 {
+    #[cfg(feature = "alloc")]
+    pub fn encode(input: &[u8]) -> String {
+        Base32Engine::STANDARD.encode(input)
+    }
+    /// Appends the encoding of `input` to `output`, so one `String` can be
+    /// reused across calls.
+    #[cfg(feature = "alloc")]
+    pub fn encode_append(input: &[u8], output: &mut String) {
+        Base32Engine::STANDARD.encode_append(input, output)
+    }
+    #[cfg(feature = "alloc")]
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base32Engine::STANDARD.decode(input)
+    }
+    /// Length of the padded encoded output for `input_len` bytes.
+    pub const fn encoded_len(input_len: usize) -> usize {
+        Base32Engine::STANDARD.encoded_len(input_len)
+    }
+    /// Upper bound of the decoded length for an input of `input_len` symbols.
+    pub const fn decoded_len_estimate(input_len: usize) -> usize {
+        Base32Engine::STANDARD.decoded_len_estimate(input_len)
+    }
+    /// Encodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+        Base32Engine::STANDARD.encode_to_slice(input, output)
+    }
+    /// Decodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
+        Base32Engine::STANDARD.decode_to_slice(input, output)
+    }
+}
 
-impl Base36 {
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let mut num = 0u128;
-        for byte in input {
-            num = (num << 8) | *byte as u128;
-        }
+#[cfg(feature = "alloc")]
+impl Encoding for Base32 {
+    fn name(&self) -> &'static str {
+        "base32"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE32_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        true
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some(Base32::encoded_len(input_len))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base32::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base32::decode(input)
+    }
+}
 
-        let mut encoded = String::new();
-        while num > 0 {
-            let remainder = num % 36;
-            num /= 36;
-            let value = match BASE36_ALPHABET.chars().nth(remainder as usize) {
+static CROCKFORD_ALPHABET: Alphabet<32> = Alphabet::new("0123456789ABCDEFGHJKMNPQRSTVWXYZ")
+    .ignore_case()
+    .with_alias(b'O', b'0')
+    .with_alias(b'o', b'0')
+    .with_alias(b'I', b'1')
+    .with_alias(b'i', b'1')
+    .with_alias(b'L', b'1')
+    .with_alias(b'l', b'1');
+/// The data symbols followed by the check symbols for values 32 to 36.
+static CROCKFORD_CHECK_ALPHABET: Alphabet<37> =
+    Alphabet::new("0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U")
+        .ignore_case()
+        .with_alias(b'O', b'0')
+        .with_alias(b'o', b'0')
+        .with_alias(b'I', b'1')
+        .with_alias(b'i', b'1')
+        .with_alias(b'L', b'1')
+        .with_alias(b'l', b'1');
+
+/// Douglas Crockford's Base32, meant for codes that people read and type.
+///
+/// The alphabet leaves out I, L, O and U. Decoding ignores case and hyphens
+/// and reads O as 0 and I and L as 1. The optional check symbol is the value
+/// modulo 37 and catches a single wrong or two swapped symbols.
+///
+/// Byte strings are packed five bits per symbol without padding, and their
+/// check symbol is taken over the bytes read as a big-endian number. A
+/// symbol count no byte string encodes to, or set bits left over in the
+/// last symbol, are rejected, so a dropped or extra symbol does not decode.
+/// Integers are written as base 32 numbers:
+///
+/// ```
+/// use simple_encode::Crockford;
+///
+/// assert_eq!(Crockford::encode_u64_with_check(1234), "16JD");
+/// assert_eq!(Crockford::decode_u64_with_check("16j-d"), Ok(1234));
+/// assert_eq!(Crockford::decode_u64("1O"), Ok(32));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Crockford {}
+
+impl Crockford {
+    #[cfg(feature = "alloc")]
+    pub fn encode(input: &[u8]) -> String {
+        Base32Engine::new(&CROCKFORD_ALPHABET)
+            .with_encode_padding(false)
+            .encode(input)
+    }
+    /// Encodes `input` followed by its check symbol.
+    #[cfg(feature = "alloc")]
+    pub fn encode_with_check(input: &[u8]) -> String {
+        let mut encoded = Crockford::encode(input);
+        encoded.push(CROCKFORD_CHECK_ALPHABET.symbol(Crockford::bytes_check(input)) as char);
+        encoded
+    }
+    #[cfg(feature = "alloc")]
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = Vec::with_capacity(input.len() * 5 / 8);
+        let mut bits = Regroup::<5, 8>::new();
+        let mut symbols = 0usize;
+        let mut last_symbol = 0;
+
+        for (i, &byte) in input.as_bytes().iter().enumerate() {
+            if byte == b'-' {
+                continue;
+            }
+            let index = match CROCKFORD_ALPHABET.value(byte) {
                 Some(v) => v,
-                None => {
-                    return Err(DecodeError::new(&format!(
-                        "get value from alphabet failed, index {}, max length {}",
-                        remainder,
-                        BASE36_ALPHABET.len(),
-                    ))
-                    .into())
-                }
+                None => return Err(DecodeError::invalid_character(input, i)),
             };
-            encoded.push(value);
+            bits.push(index, |byte| decoded.push(byte));
+            symbols += 1;
+            last_symbol = i;
         }
 
-        if encoded.is_empty() {
-            encoded.push('0');
+        // a trailing group of n bytes is written as 2, 4, 5 or 7 symbols
+        if matches!(symbols % 8, 1 | 3 | 6) {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
         }
-
-        let encoded = encoded.chars().rev().collect();
-        Ok(encoded)
+        if bits.has_trailing_bits() {
+            return Err(DecodeError::InvalidTrailingBits { index: last_symbol });
+        }
+        Ok(decoded)
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        let mut num = 0u128;
-
-        for c in input.chars() {
-            let index = match BASE36_ALPHABET.find(c) {
-                Some(i) => i,
-                None => return Err(DecodeError::new("invalid base36 character").into()),
+    /// Decodes `input` whose last symbol is its check symbol, and verifies it.
+    #[cfg(feature = "alloc")]
+    pub fn decode_with_check(input: &str) -> Result<Vec<u8>, DecodeError> {
+        let (data, check) = Crockford::split_check(input)?;
+        let decoded = Crockford::decode(data)?;
+        Crockford::verify(Crockford::bytes_check(&decoded), check)?;
+        Ok(decoded)
+    }
+    #[cfg(feature = "alloc")]
+    pub fn encode_u64(value: u64) -> String {
+        Crockford::encode_u128(value.into())
+    }
+    #[cfg(feature = "alloc")]
+    pub fn encode_u128(value: u128) -> String {
+        // 26 symbols hold 130 bits
+        let mut digits = [0u8; 26];
+        let mut start = digits.len();
+        let mut value = value;
+        loop {
+            start -= 1;
+            digits[start] = CROCKFORD_ALPHABET.symbol((value % 32) as usize);
+            value /= 32;
+            if value == 0 {
+                break;
+            }
+        }
+        String::from(core::str::from_utf8(&digits[start..]).expect("encoders only produce ASCII"))
+    }
+    /// Encodes `value` followed by its check symbol.
+    #[cfg(feature = "alloc")]
+    pub fn encode_u64_with_check(value: u64) -> String {
+        Crockford::encode_u128_with_check(value.into())
+    }
+    /// Encodes `value` followed by its check symbol.
+    #[cfg(feature = "alloc")]
+    pub fn encode_u128_with_check(value: u128) -> String {
+        let mut encoded = Crockford::encode_u128(value);
+        encoded.push(CROCKFORD_CHECK_ALPHABET.symbol((value % 37) as usize) as char);
+        encoded
+    }
+    pub fn decode_u64(input: &str) -> Result<u64, DecodeError> {
+        Crockford::decode_int(input, u64::MAX.into()).map(|value| value as u64)
+    }
+    pub fn decode_u128(input: &str) -> Result<u128, DecodeError> {
+        Crockford::decode_int(input, u128::MAX)
+    }
+    /// Decodes `input` whose last symbol is its check symbol, and verifies it.
+    pub fn decode_u64_with_check(input: &str) -> Result<u64, DecodeError> {
+        let (digits, check) = Crockford::split_check(input)?;
+        let value = Crockford::decode_u64(digits)?;
+        Crockford::verify((value % 37) as usize, check)?;
+        Ok(value)
+    }
+    /// Decodes `input` whose last symbol is its check symbol, and verifies it.
+    pub fn decode_u128_with_check(input: &str) -> Result<u128, DecodeError> {
+        let (digits, check) = Crockford::split_check(input)?;
+        let value = Crockford::decode_u128(digits)?;
+        Crockford::verify((value % 37) as usize, check)?;
+        Ok(value)
+    }
+    /// Reads `input` as a base 32 number no larger than `max`.
+    fn decode_int(input: &str, max: u128) -> Result<u128, DecodeError> {
+        let mut value = 0u128;
+        let mut symbols = 0;
+        for (i, &byte) in input.as_bytes().iter().enumerate() {
+            if byte == b'-' {
+                continue;
+            }
+            let digit = match CROCKFORD_ALPHABET.value(byte) {
+                Some(v) => v,
+                None => return Err(DecodeError::invalid_character(input, i)),
             };
-            num = num * 36 + index as u128;
+            value = value
+                .checked_mul(32)
+                .and_then(|value| value.checked_add(digit.into()))
+                .filter(|&value| value <= max)
+                .ok_or(DecodeError::Overflow { index: i })?;
+            symbols += 1;
         }
-
-        let mut decoded = Vec::new();
-        while num > 0 {
-            decoded.push((num & 0xFF) as u8);
-            num >>= 8;
+        if symbols == 0 {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
         }
-
-        decoded.reverse();
-        Ok(decoded)
+        Ok(value)
+    }
+    /// Splits the check symbol, the last one that is not a hyphen, from
+    /// the rest of `input` and returns its value.
+    fn split_check(input: &str) -> Result<(&str, usize), DecodeError> {
+        let (index, check) = input
+            .trim_end_matches('-')
+            .char_indices()
+            .next_back()
+            .ok_or(DecodeError::InvalidLength {
+                length: input.len(),
+            })?;
+        let value = u8::try_from(check)
+            .ok()
+            .and_then(|check| CROCKFORD_CHECK_ALPHABET.value(check))
+            .ok_or_else(|| DecodeError::invalid_character(input, index))?;
+        Ok((&input[..index], value.into()))
+    }
+    fn verify(expected: usize, found: usize) -> Result<(), DecodeError> {
+        if expected != found {
+            return Err(DecodeError::ChecksumMismatch {
+                expected: expected as u32,
+                found: found as u32,
+            });
+        }
+        Ok(())
+    }
+    /// Check value of a byte string: the bytes as a big-endian number
+    /// modulo 37.
+    #[cfg(feature = "alloc")]
+    fn bytes_check(input: &[u8]) -> usize {
+        input
+            .iter()
+            .fold(0, |check, &byte| (check * 256 + byte as usize) % 37)
     }
 }
 
-const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
-
-pub struct Base58 {}
+#[cfg(feature = "alloc")]
+impl Encoding for Crockford {
+    fn name(&self) -> &'static str {
+        "crockford32"
+    }
+    fn alphabet(&self) -> &'static str {
+        CROCKFORD_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some((input_len * 8).div_ceil(5))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Crockford::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Crockford::decode(input)
+    }
+}
 
-impl Base58 {
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let mut num = 0u128;
-        for byte in input {
-            num = num * 256 + *byte as u128;
+#[cfg(feature = "alloc")]
+static ZBASE32_ALPHABET: Alphabet<32> =
+    Alphabet::new("ybndrfg8ejkmcpqxot1uwisza345h769").ignore_case();
+
+/// Zooko's z-base-32, with an alphabet ordered so the symbols that are
+/// easiest to read and say come up most often.
+///
+/// The encoding works on bit strings: [`ZBase32::encode_bits`] writes only
+/// the first `bits` bits of its input, one symbol per five bits, so short
+/// keys need no byte padding:
+///
+/// ```
+/// use simple_encode::ZBase32;
+///
+/// assert_eq!(ZBase32::encode_bits(&[0x8b, 0x88, 0x80], 20), "tqre");
+/// assert_eq!(ZBase32::decode_bits("tqre", 20).unwrap(), [0x8b, 0x88, 0x80]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZBase32 {}
+
+#[cfg(feature = "alloc")]
+impl ZBase32 {
+    pub fn encode(input: &[u8]) -> String {
+        ZBase32::encode_bits(input, input.len() * 8)
+    }
+    /// Decodes whole bytes; the bits of the last symbol that do not fill a
+    /// byte are dropped.
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        ZBase32::decode_bits(input, input.len() * 5 / 8 * 8)
+    }
+    /// Encodes the first `bits` bits of `input`, most significant bit first,
+    /// into `bits.div_ceil(5)` symbols.
+    ///
+    /// # Panics
+    /// If `input` is shorter than `bits` bits.
+    pub fn encode_bits(input: &[u8], bits: usize) -> String {
+        assert!(
+            bits <= input.len() * 8,
+            "input is shorter than {} bits",
+            bits
+        );
+        (0..bits.div_ceil(5))
+            .map(|symbol| {
+                let value = (symbol * 5..symbol * 5 + 5).fold(0, |value, bit| {
+                    (value << 1) | ZBase32::bit(input, bit, bits)
+                });
+                ZBASE32_ALPHABET.symbol(value) as char
+            })
+            .collect()
+    }
+    /// Decodes a string of `bits.div_ceil(5)` symbols into `bits` bits,
+    /// returned in `bits.div_ceil(8)` bytes whose unused low bits are zero.
+    pub fn decode_bits(input: &str, bits: usize) -> Result<Vec<u8>, DecodeError> {
+        if input.len() != bits.div_ceil(5) {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
         }
 
-        let mut encoded = String::new();
-        while num > 0 {
-            let remainder = num % 58;
-            num /= 58;
-            let value = match BASE58_ALPHABET.chars().nth(remainder as usize) {
+        let mut decoded = vec![0u8; bits.div_ceil(8)];
+        for (i, &byte) in input.as_bytes().iter().enumerate() {
+            let value = match ZBASE32_ALPHABET.value(byte) {
                 Some(v) => v,
-                None => {
-                    return Err(DecodeError::new(&format!(
-                        "get value from alphabet failed, index {}, max length {}",
-                        remainder,
-                        BASE58_ALPHABET.len(),
-                    ))
-                    .into())
-                }
+                None => return Err(DecodeError::invalid_character(input, i)),
             };
-            encoded.push(value);
+            for j in 0..5 {
+                let bit = i * 5 + j;
+                if bit < bits && value & (0x10 >> j) != 0 {
+                    decoded[bit / 8] |= 0x80 >> (bit % 8);
+                }
+            }
+        }
+        Ok(decoded)
+    }
+    /// Bit `index` of `input`, or 0 past the first `bits` bits.
+    fn bit(input: &[u8], index: usize, bits: usize) -> usize {
+        if index >= bits {
+            return 0;
         }
+        ((input[index / 8] >> (7 - index % 8)) & 1) as usize
+    }
+}
 
-        for byte in input {
-            if *byte == 0 {
-                let value = BASE58_ALPHABET.chars().nth(0).unwrap();
-                encoded.push(value);
-            } else {
-                break;
-            }
+#[cfg(feature = "alloc")]
+impl Encoding for ZBase32 {
+    fn name(&self) -> &'static str {
+        "zbase32"
+    }
+    fn alphabet(&self) -> &'static str {
+        ZBASE32_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some((input_len * 8).div_ceil(5))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        ZBase32::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        ZBase32::decode(input)
+    }
+}
+
+#[cfg(feature = "alloc")]
+static BASE36_ALPHABET: Alphabet<36> = Alphabet::new("0123456789abcdefghijklmnopqrstuvwxyz");
+#[cfg(feature = "alloc")]
+static BASE36_UPPER_ALPHABET: Alphabet<36> = Alphabet::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base36 {}
+
+#[cfg(feature = "alloc")]
+impl Base36 {
+    /// Base36 encoding with the lowercase alphabet.
+    ///
+    /// Like Base58, the input is treated as one big-endian number of any
+    /// length and every leading zero byte is written as a leading `'0'`, so
+    /// `decode(encode(x)) == x` holds for every byte string. The empty input
+    /// encodes to the empty string.
+    pub fn encode(input: &[u8]) -> String {
+        radix_encode(input, &BASE36_ALPHABET)
+    }
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        radix_decode(input, &BASE36_ALPHABET)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoding for Base36 {
+    fn name(&self) -> &'static str {
+        "base36"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE36_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base36::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base36::decode(input)
+    }
+}
+
+/// [`Base36`] with the uppercase alphabet `0-9A-Z`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base36Upper {}
+
+#[cfg(feature = "alloc")]
+impl Base36Upper {
+    pub fn encode(input: &[u8]) -> String {
+        radix_encode(input, &BASE36_UPPER_ALPHABET)
+    }
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        radix_decode(input, &BASE36_UPPER_ALPHABET)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoding for Base36Upper {
+    fn name(&self) -> &'static str {
+        "base36upper"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE36_UPPER_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base36Upper::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base36Upper::decode(input)
+    }
+}
+
+/// Converts `input` from base 256 into the base given by the length of
+/// `alphabet`, writing each leading zero byte as the first symbol.
+#[cfg(feature = "alloc")]
+fn radix_encode<const N: usize>(input: &[u8], alphabet: &Alphabet<N>) -> String {
+    let base = N as u32;
+    let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+
+    // little-endian digits in the target base
+    let mut digits: Vec<u8> = Vec::with_capacity(input.len() * 2);
+    for &byte in &input[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % base) as u8;
+            carry /= base;
+        }
+        while carry > 0 {
+            digits.push((carry % base) as u8);
+            carry /= base;
         }
+    }
 
-        let encoded = encoded.chars().rev().collect();
-        Ok(encoded)
+    let mut encoded = String::with_capacity(zeros + digits.len());
+    for _ in 0..zeros {
+        encoded.push(alphabet.symbol(0) as char);
+    }
+    for &digit in digits.iter().rev() {
+        encoded.push(alphabet.symbol(digit as usize) as char);
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        let mut num = 0u128;
+    encoded
+}
 
-        for c in input.chars() {
-            let index = match BASE58_ALPHABET.find(c) {
-                Some(i) => i,
-                None => return Err(DecodeError::new("invalid base58 character").into()),
-            };
-            num = num * 58 + index as u128;
+/// Inverse of [`radix_encode`].
+#[cfg(feature = "alloc")]
+fn radix_decode<const N: usize>(
+    input: &str,
+    alphabet: &Alphabet<N>,
+) -> Result<Vec<u8>, DecodeError> {
+    let base = N as u32;
+    let zero = alphabet.symbol(0);
+    let zeros = input.bytes().take_while(|&byte| byte == zero).count();
+
+    // little-endian bytes of the decoded number
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for (i, &byte) in input.as_bytes().iter().enumerate().skip(zeros) {
+        let mut carry = match alphabet.value(byte) {
+            Some(v) => v as u32,
+            None => return Err(DecodeError::invalid_character(input, i)),
+        };
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * base;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
         }
+    }
 
-        let mut decoded = Vec::new();
-        while num > 0 {
-            decoded.push((num % 256) as u8);
-            num /= 256;
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes.iter().rev());
+    Ok(decoded)
+}
+
+#[cfg(feature = "alloc")]
+static BASE58_ALPHABET: Alphabet<58> =
+    Alphabet::new("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+#[cfg(feature = "alloc")]
+static BASE58_RIPPLE_ALPHABET: Alphabet<58> =
+    Alphabet::new("rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+#[cfg(feature = "alloc")]
+static BASE58_FLICKR_ALPHABET: Alphabet<58> =
+    Alphabet::new("123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ");
+
+/// Base58 codec with a configurable alphabet.
+///
+/// `BITCOIN`, which [`Base58`] and [`Base58Check`] use, leaves out the
+/// look-alikes `0`, `O`, `I` and `l`. `RIPPLE` orders the same symbols for
+/// XRP Ledger addresses and `FLICKR` puts lowercase first for Flickr short
+/// URLs. Any other [`Alphabet<58>`] works too, and one built in a `static`
+/// fails to compile unless it has 58 unique ASCII symbols:
+///
+/// ```
+/// use simple_encode::{Alphabet, Base58Engine};
+///
+/// assert_eq!(Base58Engine::RIPPLE.encode(b"\0Hello World"), "rJxErpTiA7PhnBMd");
+/// assert_eq!(Base58Engine::FLICKR.encode(b"\0Hello World"), "1iXf12sRWto45bmC");
+///
+/// static SORTED: Alphabet<58> =
+///     Alphabet::new("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+/// let engine = Base58Engine::new(&SORTED);
+/// assert_eq!(engine.decode("1JxF12TrwUP45BMd").unwrap(), b"\0Hello World");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy)]
+pub struct Base58Engine {
+    alphabet: &'static Alphabet<58>,
+    name: &'static str,
+}
+
+#[cfg(feature = "alloc")]
+impl Base58Engine {
+    /// Bitcoin alphabet (`1-9A-HJ-NP-Za-km-z`).
+    pub const BITCOIN: Base58Engine = Base58Engine::new(&BASE58_ALPHABET);
+    /// Ripple alphabet, used by XRP Ledger addresses.
+    pub const RIPPLE: Base58Engine =
+        Base58Engine::new(&BASE58_RIPPLE_ALPHABET).with_name("base58ripple");
+    /// Flickr alphabet, the Bitcoin one with the cases swapped.
+    pub const FLICKR: Base58Engine =
+        Base58Engine::new(&BASE58_FLICKR_ALPHABET).with_name("base58flickr");
+
+    /// Engine for `alphabet`, named `"base58"` until
+    /// [`with_name`](Base58Engine::with_name) says otherwise.
+    pub const fn new(alphabet: &'static Alphabet<58>) -> Base58Engine {
+        Base58Engine {
+            alphabet,
+            name: "base58",
         }
+    }
+    /// Sets the name that [`Encoding::name`] reports.
+    pub const fn with_name(mut self, name: &'static str) -> Base58Engine {
+        self.name = name;
+        self
+    }
+    /// The input is treated as one big-endian number of any length, and
+    /// every leading zero byte is written as the alphabet's first symbol.
+    pub fn encode(&self, input: &[u8]) -> String {
+        radix_encode(input, self.alphabet)
+    }
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        radix_decode(input, self.alphabet)
+    }
+    /// Base58Check in this alphabet: encodes `prefix`, `payload` and the
+    /// first 4 bytes of the double SHA-256 of both.
+    pub fn encode_check(&self, prefix: &[u8], payload: &[u8]) -> String {
+        let mut data = Vec::with_capacity(prefix.len() + payload.len() + 4);
+        data.extend_from_slice(prefix);
+        data.extend_from_slice(payload);
+        data.extend_from_slice(&Base58Engine::checksum(&data));
+        self.encode(&data)
+    }
+    /// Decodes Base58Check in this alphabet and verifies the checksum,
+    /// returning the first `prefix_len` bytes as the version prefix and the
+    /// rest as the payload.
+    pub fn decode_check(
+        &self,
+        input: &str,
+        prefix_len: usize,
+    ) -> Result<(Vec<u8>, Vec<u8>), DecodeError> {
+        let mut data = self.decode_checked(input)?;
+        if data.len() < prefix_len {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
+        }
+        let payload = data.split_off(prefix_len);
+        Ok((data, payload))
+    }
+    /// Decodes `input` and strips its verified checksum.
+    fn decode_checked(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut data = self.decode(input)?;
+        let Some(split) = data.len().checked_sub(4) else {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
+        };
+        let checksum = Base58Engine::checksum(&data[..split]);
+        if data[split..] != checksum {
+            let carried = [
+                data[split],
+                data[split + 1],
+                data[split + 2],
+                data[split + 3],
+            ];
+            return Err(DecodeError::ChecksumMismatch {
+                expected: u32::from_be_bytes(checksum),
+                found: u32::from_be_bytes(carried),
+            });
+        }
+        data.truncate(split);
+        Ok(data)
+    }
+    fn checksum(data: &[u8]) -> [u8; 4] {
+        let hash = sha256::digest(&sha256::digest(data));
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+}
 
-        // 添加前导零
-        for c in input.chars() {
-            if c == BASE58_ALPHABET.chars().nth(0).unwrap() {
-                decoded.push(0);
-            } else {
-                break;
+#[cfg(feature = "alloc")]
+impl Encoding for Base58Engine {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn alphabet(&self) -> &'static str {
+        self.alphabet.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base58Engine::encode(self, input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base58Engine::decode(self, input)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base58 {}
+
+#[cfg(feature = "alloc")]
+impl Base58 {
+    /// Base58 encoding with the Bitcoin alphabet.
+    ///
+    /// The input is treated as one big-endian number of any length, and every
+    /// leading zero byte is written as a leading `'1'`.
+    pub fn encode(input: &[u8]) -> String {
+        Base58Engine::BITCOIN.encode(input)
+    }
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base58Engine::BITCOIN.decode(input)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoding for Base58 {
+    fn name(&self) -> &'static str {
+        "base58"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE58_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base58::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base58::decode(input)
+    }
+}
+
+/// Base58Check, as used by Bitcoin addresses, WIF keys and extended keys:
+/// Base58 of a version prefix, the payload and the first 4 bytes of the
+/// double SHA-256 of both. [`Base58Engine::encode_check`] does the same in
+/// other alphabets.
+///
+/// ```
+/// use simple_encode::Base58Check;
+///
+/// let hash = [0x62, 0xe9, 0x07, 0xb1, 0x5c, 0xbf, 0x27, 0xd5, 0x42, 0x53, 0x99, 0xeb,
+///             0xf6, 0xf0, 0xfb, 0x50, 0xeb, 0xb8, 0x8f, 0x18];
+/// let address = Base58Check::encode(0x00, &hash);
+/// assert_eq!(address, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+/// assert_eq!(Base58Check::decode(&address).unwrap(), (0x00, hash.to_vec()));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base58Check {}
+
+#[cfg(feature = "alloc")]
+impl Base58Check {
+    /// Encodes `payload` after a single version byte.
+    pub fn encode(version: u8, payload: &[u8]) -> String {
+        Base58Engine::BITCOIN.encode_check(&[version], payload)
+    }
+    /// Encodes `payload` after a version prefix of any length, such as the
+    /// 4-byte prefixes of BIP-32 extended keys.
+    pub fn encode_with_prefix(prefix: &[u8], payload: &[u8]) -> String {
+        Base58Engine::BITCOIN.encode_check(prefix, payload)
+    }
+    /// Decodes and verifies the checksum, returning the version byte and
+    /// the payload.
+    pub fn decode(input: &str) -> Result<(u8, Vec<u8>), DecodeError> {
+        let (prefix, payload) = Base58Engine::BITCOIN.decode_check(input, 1)?;
+        Ok((prefix[0], payload))
+    }
+    /// Decodes and verifies the checksum, returning the first `prefix_len`
+    /// bytes as the version prefix and the rest as the payload.
+    pub fn decode_with_prefix(
+        input: &str,
+        prefix_len: usize,
+    ) -> Result<(Vec<u8>, Vec<u8>), DecodeError> {
+        Base58Engine::BITCOIN.decode_check(input, prefix_len)
+    }
+}
+
+/// The whole input is the versioned payload; decoding returns it with the
+/// checksum stripped.
+#[cfg(feature = "alloc")]
+impl Encoding for Base58Check {
+    fn name(&self) -> &'static str {
+        "base58check"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE58_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base58Engine::BITCOIN.encode_check(&[], input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base58Engine::BITCOIN.decode_checked(input)
+    }
+}
+
+/// Monero's Base58: the input is split into 8-byte blocks that are each
+/// encoded into exactly 11 symbols of the Bitcoin alphabet, so no big-number
+/// arithmetic is needed and the encoded length only depends on the input
+/// length.
+///
+/// ```
+/// use simple_encode::MoneroBase58;
+///
+/// assert_eq!(MoneroBase58::encode(&[0xff; 8]), "jpXCZedGfVQ");
+/// assert_eq!(MoneroBase58::encode(&[0x01, 0x00]), "15R");
+/// assert_eq!(MoneroBase58::decode("15R").unwrap(), [0x01, 0x00]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MoneroBase58 {}
+
+/// Symbols for a block of 0 to 8 bytes.
+#[cfg(feature = "alloc")]
+const MONERO_BLOCK_SIZES: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
+#[cfg(feature = "alloc")]
+impl MoneroBase58 {
+    pub fn encode(input: &[u8]) -> String {
+        let mut encoded = String::with_capacity(MoneroBase58::encoded_len(input.len()));
+        let mut symbols = [0u8; 11];
+        for block in input.chunks(8) {
+            let mut value = block
+                .iter()
+                .fold(0u64, |value, &byte| (value << 8) | byte as u64);
+            let symbols = &mut symbols[..MONERO_BLOCK_SIZES[block.len()]];
+            for symbol in symbols.iter_mut().rev() {
+                *symbol = BASE58_ALPHABET.symbol((value % 58) as usize);
+                value /= 58;
             }
+            encoded.extend(symbols.iter().map(|&symbol| symbol as char));
+        }
+        encoded
+    }
+    /// Decodes blocks of 11 symbols and a shorter final block.
+    ///
+    /// Fails with [`DecodeError::InvalidLength`] if the final block has a
+    /// length no byte count encodes to, and with [`DecodeError::Overflow`] at
+    /// the start of a block whose value does not fit in its bytes.
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        let last = input.len() % 11;
+        let Some(last_bytes) = MONERO_BLOCK_SIZES.iter().position(|&size| size == last) else {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
+        };
+
+        let mut decoded = Vec::with_capacity(input.len() / 11 * 8 + last_bytes);
+        for (n, block) in input.as_bytes().chunks(11).enumerate() {
+            let start = n * 11;
+            let bytes = if block.len() == 11 { 8 } else { last_bytes };
+            let mut value = 0u64;
+            for (i, &byte) in block.iter().enumerate() {
+                let digit = match BASE58_ALPHABET.value(byte) {
+                    Some(v) => v as u64,
+                    None => return Err(DecodeError::invalid_character(input, start + i)),
+                };
+                value = value
+                    .checked_mul(58)
+                    .and_then(|value| value.checked_add(digit))
+                    .ok_or(DecodeError::Overflow { index: start })?;
+            }
+            if bytes < 8 && value >> (bytes * 8) != 0 {
+                return Err(DecodeError::Overflow { index: start });
+            }
+            decoded.extend_from_slice(&value.to_be_bytes()[8 - bytes..]);
         }
-
-        decoded.reverse();
         Ok(decoded)
     }
+    pub const fn encoded_len(input_len: usize) -> usize {
+        input_len / 8 * 11 + MONERO_BLOCK_SIZES[input_len % 8]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoding for MoneroBase58 {
+    fn name(&self) -> &'static str {
+        "base58monero"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE58_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some(MoneroBase58::encoded_len(input_len))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        MoneroBase58::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        MoneroBase58::decode(input)
+    }
 }
 
-const BASE62_ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+#[cfg(feature = "alloc")]
+static BASE62_ALPHABET: Alphabet<62> =
+    Alphabet::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
 
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Base62 {}
 
+#[cfg(feature = "alloc")]
 impl Base62 {
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let mut num = 0u128;
-        for byte in input {
-            num = num * 256 + *byte as u128;
-        }
+    /// Base62 encoding, treating the input as one big-endian number of any
+    /// length. Every leading zero byte is written as a leading `'0'`.
+    pub fn encode(input: &[u8]) -> String {
+        radix_encode(input, &BASE62_ALPHABET)
+    }
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        radix_decode(input, &BASE62_ALPHABET)
+    }
+}
 
-        let mut encoded = String::new();
-        while num > 0 {
-            let remainder = num % 62;
-            num /= 62;
-            let value = match BASE62_ALPHABET.chars().nth(remainder as usize) {
-                Some(v) => v,
-                None => {
-                    return Err(DecodeError::new(&format!(
-                        "get value from alphabet failed, index {}, max length {}",
-                        remainder,
-                        BASE62_ALPHABET.len(),
-                    ))
-                    .into())
-                }
-            };
-            encoded.push(value);
+#[cfg(feature = "alloc")]
+impl Encoding for Base62 {
+    fn name(&self) -> &'static str {
+        "base62"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE62_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base62::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base62::decode(input)
+    }
+}
+
+static BASE64_ALPHABET: Alphabet<64> =
+    Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+static BASE64_URL_SAFE_ALPHABET: Alphabet<64> =
+    Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+
+/// How much of RFC 4648 a decoder enforces beyond the alphabet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Accepts only the canonical encoding of some input: padding, if any,
+    /// is the exact amount needed and ends the input, the length is one an
+    /// encoder can produce and the bits left over in the last symbol are
+    /// zero, so every byte string has a single accepted encoding.
+    Strict,
+    /// Stops at the first `'='` and ignores the rest of the input, drops a
+    /// leftover symbol that does not complete a byte and ignores the unused
+    /// bits of the last symbol. Several strings decode to the same bytes,
+    /// so do not compare or sign data decoded in this mode.
+    Lenient,
+}
+
+/// Line separator written between the lines of wrapped output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `"\n"`
+    Lf,
+    /// `"\r\n"`, as required by MIME.
+    CrLf,
+}
+
+impl LineEnding {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
         }
+    }
+}
 
-        for byte in input {
-            if *byte == 0 {
-                encoded.push(BASE62_ALPHABET.chars().nth(0).unwrap());
-            } else {
-                break;
+/// Characters outside the alphabet that a decoder skips instead of
+/// rejecting. `'='` is never skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeIgnore {
+    /// Every character must be a symbol or padding.
+    Nothing,
+    /// ASCII whitespace, which covers wrapped lines with either ending.
+    Whitespace,
+    /// Every character outside the alphabet, like GNU `base64 -i` and the
+    /// RFC 2045 decoder.
+    Garbage,
+}
+
+/// Base64 codec with a configurable alphabet, padding and line wrapping.
+///
+/// The associated constants cover RFC 4648 §4 (`STANDARD`) and the URL and
+/// filename safe alphabet of §5 (`URL_SAFE`), each with and without padding,
+/// plus the wrapped bodies of MIME and PEM:
+///
+/// ```
+/// use simple_encode::Base64Engine;
+///
+/// let token = Base64Engine::URL_SAFE_NO_PAD.encode(&[0xfb, 0xff]);
+/// assert_eq!(token, "-_8");
+/// assert_eq!(Base64Engine::URL_SAFE_NO_PAD.decode(&token).unwrap(), [0xfb, 0xff]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Base64Engine {
+    alphabet: &'static Alphabet<64>,
+    encode_padding: bool,
+    /// line width and separator of wrapped output
+    line_wrap: Option<(usize, LineEnding)>,
+    decode_padding: DecodePadding,
+    decode_mode: DecodeMode,
+    decode_ignore: DecodeIgnore,
+}
+
+impl Base64Engine {
+    /// Standard alphabet, padded output, padding required on decode.
+    pub const STANDARD: Base64Engine = Base64Engine::new(&BASE64_ALPHABET);
+    /// Standard alphabet without padding.
+    pub const STANDARD_NO_PAD: Base64Engine = Base64Engine::new(&BASE64_ALPHABET)
+        .with_encode_padding(false)
+        .with_decode_padding(DecodePadding::Forbidden);
+    /// URL-safe alphabet (`-` and `_`), padded output, padding required on
+    /// decode.
+    pub const URL_SAFE: Base64Engine = Base64Engine::new(&BASE64_URL_SAFE_ALPHABET);
+    /// URL-safe alphabet without padding, as used by JWT.
+    pub const URL_SAFE_NO_PAD: Base64Engine = Base64Engine::new(&BASE64_URL_SAFE_ALPHABET)
+        .with_encode_padding(false)
+        .with_decode_padding(DecodePadding::Forbidden);
+    /// RFC 2045 content transfer encoding: 76 column lines ended by CRLF,
+    /// skipping any character outside the alphabet on decode.
+    pub const MIME: Base64Engine = Base64Engine::new(&BASE64_ALPHABET)
+        .with_line_wrap(76, LineEnding::CrLf)
+        .with_decode_ignore(DecodeIgnore::Garbage);
+    /// RFC 7468 PEM body: 64 column lines ended by LF, skipping whitespace on
+    /// decode.
+    pub const PEM: Base64Engine = Base64Engine::new(&BASE64_ALPHABET)
+        .with_line_wrap(64, LineEnding::Lf)
+        .with_decode_ignore(DecodeIgnore::Whitespace);
+
+    /// Engine for `alphabet` that pads its output on a single line and
+    /// requires canonical, padded input on decode.
+    pub const fn new(alphabet: &'static Alphabet<64>) -> Base64Engine {
+        Base64Engine {
+            alphabet,
+            encode_padding: true,
+            line_wrap: None,
+            decode_padding: DecodePadding::Required,
+            decode_mode: DecodeMode::Strict,
+            decode_ignore: DecodeIgnore::Nothing,
+        }
+    }
+    /// Whether `encode` pads its output with `=` to a multiple of 4.
+    pub const fn with_encode_padding(mut self, padding: bool) -> Base64Engine {
+        self.encode_padding = padding;
+        self
+    }
+    /// Splits encoded output into lines of `width` symbols separated by
+    /// `ending`. The last line is not terminated.
+    ///
+    /// # Panics
+    /// If `width` is zero.
+    pub const fn with_line_wrap(mut self, width: usize, ending: LineEnding) -> Base64Engine {
+        assert!(width > 0, "line width must not be zero");
+        self.line_wrap = Some((width, ending));
+        self
+    }
+    /// How `decode` treats missing or present padding.
+    pub const fn with_decode_padding(mut self, padding: DecodePadding) -> Base64Engine {
+        self.decode_padding = padding;
+        self
+    }
+    /// Selects [`DecodeMode::Strict`] (the default) or
+    /// [`DecodeMode::Lenient`] decoding.
+    pub const fn with_decode_mode(mut self, mode: DecodeMode) -> Base64Engine {
+        self.decode_mode = mode;
+        self
+    }
+    /// Which characters outside the alphabet `decode` skips.
+    pub const fn with_decode_ignore(mut self, ignore: DecodeIgnore) -> Base64Engine {
+        self.decode_ignore = ignore;
+        self
+    }
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, input: &[u8]) -> String {
+        let mut encoded = String::with_capacity(self.encoded_len(input.len()));
+        self.encode_append(input, &mut encoded);
+        encoded
+    }
+    /// Appends the encoding of `input` to `output`, so one `String` can be
+    /// reused across calls.
+    #[cfg(feature = "alloc")]
+    pub fn encode_append(&self, input: &[u8], output: &mut String) {
+        let Some((width, ending)) = self.line_wrap else {
+            append_blocks(
+                input,
+                output,
+                |len| self.unwrapped_len(len),
+                |input, output| self.encode_block(input, output),
+            );
+            return;
+        };
+
+        let mut unwrapped = String::new();
+        append_blocks(
+            input,
+            &mut unwrapped,
+            |len| self.unwrapped_len(len),
+            |input, output| self.encode_block(input, output),
+        );
+        output.reserve(self.encoded_len(input.len()));
+        for (i, line) in unwrapped.as_bytes().chunks(width).enumerate() {
+            if i > 0 {
+                output.push_str(ending.as_str());
             }
+            output.push_str(core::str::from_utf8(line).expect("encoders only produce ASCII"));
         }
-
-        let encoded = encoded.chars().rev().collect();
-        Ok(encoded)
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        let mut num = 0u128;
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = vec![0u8; self.decoded_len(input)];
+        let len = self.decode_to_slice(input, &mut decoded)?;
+        decoded.truncate(len);
+        Ok(decoded)
+    }
+    /// Length of the encoded output for `input_len` bytes, including line
+    /// separators.
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        let len = self.unwrapped_len(input_len);
+        match self.line_wrap {
+            Some((width, ending)) if len > 0 => len + (len - 1) / width * ending.as_str().len(),
+            _ => len,
+        }
+    }
+    /// Upper bound of the decoded length for an input of `input_len` symbols.
+    pub const fn decoded_len_estimate(&self, input_len: usize) -> usize {
+        input_len / 4 * 3 + input_len % 4 * 3 / 4
+    }
+    /// Encodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn encode_to_slice(&self, input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+        let len = self.encoded_len(input.len());
+        let output = output
+            .get_mut(..len)
+            .ok_or(EncodeError::BufferTooSmall { needed: len })?;
+        let unwrapped = self.unwrapped_len(input.len());
+        self.encode_block(input, &mut output[..unwrapped]);
+        if let Some((width, ending)) = self.line_wrap {
+            wrap_lines(output, unwrapped, width, ending.as_str().as_bytes());
+        }
+        Ok(len)
+    }
+    /// Decodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn decode_to_slice(&self, input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let needed = self.decoded_len(input);
+        if output.len() < needed {
+            return Err(DecodeError::BufferTooSmall { needed });
+        }
 
-        for c in input.chars() {
-            let index = match BASE62_ALPHABET.find(c) {
-                Some(i) => i,
-                None => return Err(DecodeError::new("invalid base62 character").into()),
+        let mut written = 0;
+        let mut buffer = 0u32;
+        let mut bits_left = 0;
+        let mut symbols = 0usize;
+        let mut last_symbol = 0;
+        let mut padding = 0;
+        let mut padding_at = None;
+
+        for (i, &byte) in input.as_bytes().iter().enumerate() {
+            let index = match self.alphabet.value(byte) {
+                Some(v) => v,
+                None if byte == b'=' => {
+                    padding_at.get_or_insert(i);
+                    padding += 1;
+                    if self.decode_mode == DecodeMode::Lenient {
+                        break;
+                    }
+                    continue;
+                }
+                None if self.skips(byte) => continue,
+                None => return Err(DecodeError::invalid_character(input, i)),
             };
-            num = num * 62 + index as u128;
-        }
+            // only strict decoding reads on after padding
+            if padding > 0 {
+                return Err(DecodeError::InvalidPadding { index: i });
+            }
+            buffer = (buffer << 6) | (index as u32);
+            bits_left += 6;
+            symbols += 1;
+            last_symbol = i;
 
-        let mut decoded = Vec::new();
-        while num > 0 {
-            decoded.push((num % 256) as u8);
-            num /= 256;
+            if bits_left >= 8 {
+                bits_left -= 8;
+                output[written] = (buffer >> bits_left) as u8;
+                written += 1;
+            }
         }
 
-        for c in input.chars() {
-            if c == BASE62_ALPHABET.chars().nth(0).unwrap() {
-                decoded.push(0);
-            } else {
-                break;
+        if self.decode_mode == DecodeMode::Strict {
+            if symbols % 4 == 1 {
+                return Err(DecodeError::InvalidLength {
+                    length: input.len(),
+                });
+            }
+            if let Some(index) = padding_at {
+                if padding != (4 - symbols % 4) % 4 {
+                    return Err(DecodeError::InvalidPadding { index });
+                }
+            }
+            if buffer & ((1 << bits_left) - 1) != 0 {
+                return Err(DecodeError::InvalidTrailingBits { index: last_symbol });
             }
         }
 
-        decoded.reverse();
-        Ok(decoded)
+        match (self.decode_padding, padding_at) {
+            (DecodePadding::Forbidden, Some(index)) => Err(DecodeError::InvalidPadding { index }),
+            (DecodePadding::Required, None) if !symbols.is_multiple_of(4) => {
+                Err(DecodeError::InvalidPadding { index: input.len() })
+            }
+            _ => Ok(written),
+        }
     }
-}
-
-const BASE64_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-
-pub struct Base64 {}
-
-impl Base64 {
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let mut encoded = String::new();
+    /// Number of bytes `decode_to_slice` writes for `input`, counting the
+    /// symbols up to the first `'='`.
+    fn decoded_len(&self, input: &str) -> usize {
+        let symbols = match self.decode_ignore {
+            DecodeIgnore::Nothing => input.find('=').unwrap_or(input.len()),
+            _ => input
+                .bytes()
+                .take_while(|&byte| byte != b'=')
+                .filter(|&byte| self.alphabet.value(byte).is_some())
+                .count(),
+        };
+        symbols * 6 / 8
+    }
+    /// Whether `decode` passes over `byte` as neither a symbol nor padding.
+    fn skips(&self, byte: u8) -> bool {
+        if byte == b'=' || self.alphabet.value(byte).is_some() {
+            return false;
+        }
+        match self.decode_ignore {
+            DecodeIgnore::Nothing => false,
+            DecodeIgnore::Whitespace => byte.is_ascii_whitespace(),
+            DecodeIgnore::Garbage => true,
+        }
+    }
+    /// Length of the encoded output for `input_len` bytes on a single line.
+    const fn unwrapped_len(&self, input_len: usize) -> usize {
+        if self.encode_padding {
+            return input_len.div_ceil(3) * 4;
+        }
+        // a trailing group of n bytes is written as n + 1 symbols
+        let tail = match input_len % 3 {
+            0 => 0,
+            n => n + 1,
+        };
+        input_len / 3 * 4 + tail
+    }
+    /// Writes the encoding of `input` into `output`, which must be exactly
+    /// `unwrapped_len(input.len())` long.
+    fn encode_block(&self, input: &[u8], output: &mut [u8]) {
+        let mut written = 0;
         let mut buffer = 0u32;
         let mut bits_left = 0;
 
-        for byte in input {
-            buffer = (buffer << 8) | (*byte as u32);
+        for &byte in input {
+            buffer = (buffer << 8) | (byte as u32);
             bits_left += 8;
 
             while bits_left >= 6 {
                 bits_left -= 6;
                 let index = (buffer >> bits_left) & 0b111111;
-                let value = match BASE64_ALPHABET.chars().nth(index as usize) {
-                    Some(v) => v,
-                    None => {
-                        return Err(DecodeError::new(&format!(
-                            "get value from alphabet failed, index {}, max length {}",
-                            index,
-                            BASE64_ALPHABET.len(),
-                        ))
-                        .into())
-                    }
-                };
-                encoded.push(value);
+                output[written] = self.alphabet.symbol(index as usize);
+                written += 1;
             }
         }
 
         if bits_left > 0 {
             let index = (buffer << (6 - bits_left)) & 0b111111;
-            let value = match BASE64_ALPHABET.chars().nth(index as usize) {
-                Some(v) => v,
-                None => {
-                    return Err(DecodeError::new(&format!(
-                        "get value from alphabet failed, index {}, max length {}",
-                        index,
-                        BASE64_ALPHABET.len(),
-                    ))
-                    .into())
-                }
-            };
-            encoded.push(value);
+            output[written] = self.alphabet.symbol(index as usize);
+            written += 1;
         }
 
-        while encoded.len() % 4 != 0 {
-            encoded.push('=');
+        for pad in &mut output[written..] {
+            *pad = b'=';
         }
-
-        Ok(encoded)
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        let mut decoded = Vec::new();
-        let mut buffer = 0u32;
-        let mut bits_left = 0;
+}
 
-        for c in input.chars() {
-            if c == '=' {
-                break;
-            }
+#[cfg(feature = "alloc")]
+impl Encoding for Base64Engine {
+    fn name(&self) -> &'static str {
+        if core::ptr::eq(self.alphabet, &BASE64_URL_SAFE_ALPHABET) {
+            "base64url"
+        } else {
+            "base64"
+        }
+    }
+    fn alphabet(&self) -> &'static str {
+        self.alphabet.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        self.encode_padding
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some(Base64Engine::encoded_len(self, input_len))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base64Engine::encode(self, input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base64Engine::decode(self, input)
+    }
+}
 
-            let index = match BASE64_ALPHABET.find(c) {
-                Some(i) => i,
-                None => return Err(DecodeError::new("invalid base64 character").into()),
-            };
-            buffer = (buffer << 6) | (index as u32);
-            bits_left += 6;
+/// Standard padded Base64 of RFC 4648, the [`Base64Engine::STANDARD`] preset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base64 {}
 
-            if bits_left >= 8 {
-                bits_left -= 8;
-                decoded.push((buffer >> bits_left) as u8);
-            }
-        }
+impl Base64 {
+    #[cfg(feature = "alloc")]
+    pub fn encode(input: &[u8]) -> String {
+        Base64Engine::STANDARD.encode(input)
+    }
+    /// Appends the encoding of `input` to `output`, so one `String` can be
+    /// reused across calls.
+    #[cfg(feature = "alloc")]
+    pub fn encode_append(input: &[u8], output: &mut String) {
+        Base64Engine::STANDARD.encode_append(input, output)
+    }
+    #[cfg(feature = "alloc")]
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base64Engine::STANDARD.decode(input)
+    }
+    /// Length of the padded encoded output for `input_len` bytes.
+    pub const fn encoded_len(input_len: usize) -> usize {
+        Base64Engine::STANDARD.encoded_len(input_len)
+    }
+    /// Upper bound of the decoded length for an input of `input_len` symbols.
+    pub const fn decoded_len_estimate(input_len: usize) -> usize {
+        Base64Engine::STANDARD.decoded_len_estimate(input_len)
+    }
+    /// Encodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+        Base64Engine::STANDARD.encode_to_slice(input, output)
+    }
+    /// Decodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
+        Base64Engine::STANDARD.decode_to_slice(input, output)
+    }
+}
 
-        Ok(decoded)
+#[cfg(feature = "alloc")]
+impl Encoding for Base64 {
+    fn name(&self) -> &'static str {
+        "base64"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE64_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        true
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some(Base64::encoded_len(input_len))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base64::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base64::decode(input)
     }
 }
 
-const BASE85_ALPHABET: &str =
-    "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+static BASE85_ALPHABET: Alphabet<85> = Alphabet::new(
+    "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+);
 
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Base85 {}
 
 impl Base85 {
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let mut encoded = String::new();
-        let mut buffer = 0u32;
-        let mut buffer_length = 0;
+    #[cfg(feature = "alloc")]
+    pub fn encode(input: &[u8]) -> String {
+        let mut encoded = String::with_capacity(Base85::encoded_len(input.len()));
+        Base85::encode_append(input, &mut encoded);
+        encoded
+    }
+    /// Appends the encoding of `input` to `output`, so one `String` can be
+    /// reused across calls.
+    #[cfg(feature = "alloc")]
+    pub fn encode_append(input: &[u8], output: &mut String) {
+        append_blocks(input, output, Base85::encoded_len, Base85::encode_block);
+    }
+    #[cfg(feature = "alloc")]
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = vec![0u8; Base85::decoded_len_estimate(input.len())];
+        let len = Base85::decode_to_slice(input, &mut decoded)?;
+        decoded.truncate(len);
+        Ok(decoded)
+    }
+    /// Length of the encoded output for `input_len` bytes.
+    pub const fn encoded_len(input_len: usize) -> usize {
+        // a trailing group of n bytes is written as n + 1 symbols
+        let tail = match input_len % 4 {
+            0 => 0,
+            n => n + 1,
+        };
+        input_len / 4 * 5 + tail
+    }
+    /// Upper bound of the decoded length for an input of `input_len` symbols.
+    pub const fn decoded_len_estimate(input_len: usize) -> usize {
+        let tail = match input_len % 5 {
+            0 => 0,
+            n => n - 1,
+        };
+        input_len / 5 * 4 + tail
+    }
+    /// Encodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+        let len = Base85::encoded_len(input.len());
+        let output = output
+            .get_mut(..len)
+            .ok_or(EncodeError::BufferTooSmall { needed: len })?;
+        Base85::encode_block(input, output);
+        Ok(len)
+    }
+    /// Decodes `input` into the front of `output` without allocating and
+    /// returns the number of bytes written.
+    pub fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let needed = Base85::decoded_len_estimate(input.len());
+        if output.len() < needed {
+            return Err(DecodeError::BufferTooSmall { needed });
+        }
 
-        for &byte in input {
-            buffer = (buffer << 8) | byte as u32;
-            buffer_length += 1;
-            if buffer_length == 4 {
-                for _ in 0..5 {
-                    let index = (buffer / 85u32.pow(4)) as usize;
-                    encoded.push(BASE85_ALPHABET.chars().nth(index).unwrap());
-                    buffer %= 85u32.pow(4);
-                    buffer *= 85;
-                }
-                buffer = 0;
-                buffer_length = 0;
-            }
+        let mut written = 0;
+        let mut push = |bytes: &[u8]| {
+            output[written..written + bytes.len()].copy_from_slice(bytes);
+            written += bytes.len();
+        };
+        let mut group = Base85Group::default();
+        for i in 0..input.len() {
+            group.decode(&BASE85_ALPHABET, input, i, &mut push)?;
+        }
+        group.decode_finish(input, input.len(), &mut push)?;
+        Ok(written)
+    }
+    /// Writes the encoding of `input` into `output`, which must be exactly
+    /// `encoded_len(input.len())` long.
+    fn encode_block(input: &[u8], output: &mut [u8]) {
+        let mut written = 0;
+        for chunk in input.chunks(4) {
+            Base85Group::encode(&BASE85_ALPHABET, chunk, |symbol| {
+                output[written] = symbol;
+                written += 1;
+            });
         }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoding for Base85 {
+    fn name(&self) -> &'static str {
+        "base85"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE85_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        Some(Base85::encoded_len(input_len))
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base85::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base85::decode(input)
+    }
+}
+
+/// Digits of a base-85 group, which stands for four bytes. [`Base85`] and
+/// [`Ascii85Engine`] share it and differ only in their alphabets and
+/// shortcuts.
+#[derive(Debug, Default)]
+struct Base85Group {
+    /// value of the digits read so far
+    value: u64,
+    /// number of digits read so far
+    digits: usize,
+}
 
-        if buffer_length > 0 {
-            for _ in 0..(4 - buffer_length) {
-                buffer <<= 8;
+impl Base85Group {
+    /// Passes the digits of `chunk`, up to four bytes, to `push`.
+    fn encode(alphabet: &Alphabet<85>, chunk: &[u8], mut push: impl FnMut(u8)) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = alphabet.symbol((value % 85) as usize);
+            value /= 85;
+        }
+        // a partial group of n bytes only needs its first n + 1 digits
+        for &digit in &digits[..chunk.len() + 1] {
+            push(digit);
+        }
+    }
+    /// Reads the symbol at byte `index` of `input`, passing the four bytes
+    /// to `push` once it completes the group.
+    fn decode(
+        &mut self,
+        alphabet: &Alphabet<85>,
+        input: &str,
+        index: usize,
+        mut push: impl FnMut(&[u8]),
+    ) -> Result<(), DecodeError> {
+        let digit = alphabet
+            .value(input.as_bytes()[index])
+            .ok_or_else(|| DecodeError::invalid_character(input, index))?;
+        self.value = self.value * 85 + digit as u64;
+        self.digits += 1;
+        if self.digits == 5 {
+            if self.value > u32::MAX as u64 {
+                return Err(DecodeError::Overflow { index });
+            }
+            push(&(self.value as u32).to_be_bytes());
+            *self = Base85Group::default();
+        }
+        Ok(())
+    }
+    /// Whether the next symbol starts a group.
+    #[cfg(feature = "alloc")]
+    fn is_empty(&self) -> bool {
+        self.digits == 0
+    }
+    /// Decodes a partial last group, whose data ends at byte `end` of
+    /// `input`, and passes its bytes to `push`.
+    fn decode_finish(
+        self,
+        input: &str,
+        end: usize,
+        mut push: impl FnMut(&[u8]),
+    ) -> Result<(), DecodeError> {
+        if self.digits == 1 {
+            return Err(DecodeError::InvalidLength {
+                length: input.len(),
+            });
+        }
+        if self.digits > 0 {
+            // the encoder truncated a zero padded group, so pad with the highest digit
+            let mut value = self.value;
+            for _ in self.digits..5 {
+                value = value * 85 + 84;
             }
-            for _ in 0..(buffer_length + 1) {
-                let index = (buffer / 85u32.pow(4)) as usize;
-                encoded.push(BASE85_ALPHABET.chars().nth(index).unwrap());
-                buffer %= 85u32.pow(4);
-                buffer *= 85;
+            if value > u32::MAX as u64 {
+                return Err(DecodeError::Overflow { index: end });
             }
+            push(&(value as u32).to_be_bytes()[..self.digits - 1]);
         }
-
-        Ok(encoded)
+        Ok(())
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        let mut decoded = Vec::new();
-        let mut buffer = 0u32;
-        let mut buffer_length = 0;
-        let mut base85_chars = input.chars().rev();
+}
+
+#[cfg(feature = "alloc")]
+static ASCII85_ALPHABET: Alphabet<85> = Alphabet::new(
+    "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+);
+
+/// Ascii85 codec as used by PostScript, PDF and btoa: the symbols `!` to
+/// `u`, with `z` standing for a group of four zero bytes.
+///
+/// `ADOBE`, which [`Ascii85`] uses, frames its output in `<~` and `~>`;
+/// on decode the `<~` is optional, as PDF streams leave it out. `BTOA` has
+/// no framing and also writes a group of four spaces as `y`. Decoding skips
+/// whitespace anywhere in the data.
+///
+/// ```
+/// use simple_encode::Ascii85Engine;
+///
+/// assert_eq!(Ascii85Engine::ADOBE.encode(b"Hello, world"), "<~87cURD_*#TDfTZ)~>");
+/// assert_eq!(Ascii85Engine::BTOA.encode(b"\0\0\0\0    "), "zy");
+///
+/// let pdf_stream = "87cURD_*#T\nDfTZ)~>\n";
+/// assert_eq!(Ascii85Engine::ADOBE.decode(pdf_stream).unwrap(), b"Hello, world");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy)]
+pub struct Ascii85Engine {
+    delimiters: bool,
+    space_shortcut: bool,
+    name: &'static str,
+}
 
-        while let Some(c) = base85_chars.next() {
-            let index = match BASE85_ALPHABET.find(c) {
-                Some(i) => i,
-                None => return Err(DecodeError::new("invalid base85 character").into()),
+#[cfg(feature = "alloc")]
+impl Ascii85Engine {
+    /// `<~` and `~>` framing, `z` shortcut only.
+    pub const ADOBE: Ascii85Engine = Ascii85Engine::new().with_delimiters(true);
+    /// No framing, `z` and `y` shortcuts.
+    pub const BTOA: Ascii85Engine = Ascii85Engine::new()
+        .with_space_shortcut(true)
+        .with_name("btoa");
+
+    /// Engine without framing or `y` shortcut, named `"ascii85"`.
+    pub const fn new() -> Ascii85Engine {
+        Ascii85Engine {
+            delimiters: false,
+            space_shortcut: false,
+            name: "ascii85",
+        }
+    }
+    /// Whether `encode` frames its output in `<~` and `~>`, and `decode`
+    /// requires the `~>` and accepts a leading `<~`.
+    pub const fn with_delimiters(mut self, delimiters: bool) -> Ascii85Engine {
+        self.delimiters = delimiters;
+        self
+    }
+    /// Whether a group of four spaces is written as `y`, and `y` is accepted
+    /// on decode.
+    pub const fn with_space_shortcut(mut self, space_shortcut: bool) -> Ascii85Engine {
+        self.space_shortcut = space_shortcut;
+        self
+    }
+    /// Sets the name that [`Encoding::name`] reports.
+    pub const fn with_name(mut self, name: &'static str) -> Ascii85Engine {
+        self.name = name;
+        self
+    }
+    pub fn encode(&self, input: &[u8]) -> String {
+        let mut encoded = String::with_capacity(input.len().div_ceil(4) * 5 + 4);
+        if self.delimiters {
+            encoded.push_str("<~");
+        }
+        for chunk in input.chunks(4) {
+            // only whole groups are abbreviated
+            if chunk == [0; 4] {
+                encoded.push('z');
+            } else if self.space_shortcut && chunk == b"    " {
+                encoded.push('y');
+            } else {
+                Base85Group::encode(&ASCII85_ALPHABET, chunk, |d| encoded.push(d as char));
+            }
+        }
+        if self.delimiters {
+            encoded.push_str("~>");
+        }
+        encoded
+    }
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        let (start, end) = if self.delimiters {
+            let leading = input.len() - input.trim_start().len();
+            let start = if input[leading..].starts_with("<~") {
+                leading + 2
+            } else {
+                0
             };
-            buffer = buffer * 85 + index as u32;
-            buffer_length += 1;
-            if buffer_length == 5 {
-                for _ in 0..4 {
-                    decoded.push((buffer >> 24) as u8);
-                    buffer <<= 8;
+            let end = match input[start..].find("~>") {
+                Some(i) => start + i,
+                None => {
+                    return Err(DecodeError::InvalidLength {
+                        length: input.len(),
+                    })
                 }
-                buffer = 0;
-                buffer_length = 0;
+            };
+            if let Some(i) = input[end + 2..].find(|c: char| !c.is_ascii_whitespace()) {
+                return Err(DecodeError::invalid_character(input, end + 2 + i));
             }
-        }
-
-        if buffer_length > 0 {
-            for _ in 0..(5 - buffer_length) {
-                buffer *= 85;
+            (start, end)
+        } else {
+            (0, input.len())
+        };
+
+        let mut decoded = Vec::with_capacity((end - start) / 5 * 4 + 4);
+        let mut group = Base85Group::default();
+        for (i, &byte) in input.as_bytes().iter().enumerate().take(end).skip(start) {
+            if byte.is_ascii_whitespace() {
+                continue;
             }
-            for _ in 0..(buffer_length - 1) {
-                decoded.push((buffer >> 24) as u8);
-                buffer <<= 8;
+            if group.is_empty() && byte == b'z' {
+                decoded.extend_from_slice(&[0; 4]);
+            } else if group.is_empty() && byte == b'y' && self.space_shortcut {
+                decoded.extend_from_slice(b"    ");
+            } else {
+                group.decode(&ASCII85_ALPHABET, input, i, |bytes| {
+                    decoded.extend_from_slice(bytes)
+                })?;
             }
         }
-
-        decoded.reverse();
+        group.decode_finish(input, end, |bytes| decoded.extend_from_slice(bytes))?;
         Ok(decoded)
     }
 }
 
-const BASE91_ALPHABET: &str =
-    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"";
+#[cfg(feature = "alloc")]
+impl Default for Ascii85Engine {
+    fn default() -> Ascii85Engine {
+        Ascii85Engine::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoding for Ascii85Engine {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn alphabet(&self) -> &'static str {
+        ASCII85_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        // depends on how many groups are abbreviated
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Ascii85Engine::encode(self, input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Ascii85Engine::decode(self, input)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ascii85 {}
+
+#[cfg(feature = "alloc")]
+impl Ascii85 {
+    /// Adobe Ascii85, framed in `<~` and `~>`.
+    pub fn encode(input: &[u8]) -> String {
+        Ascii85Engine::ADOBE.encode(input)
+    }
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        Ascii85Engine::ADOBE.decode(input)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encoding for Ascii85 {
+    fn name(&self) -> &'static str {
+        "ascii85"
+    }
+    fn alphabet(&self) -> &'static str {
+        ASCII85_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Ascii85::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Ascii85::decode(input)
+    }
+}
+
+#[cfg(feature = "alloc")]
+static BASE91_ALPHABET: Alphabet<91> = Alphabet::new(
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"",
+);
 
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Base91 {}
 
+#[cfg(feature = "alloc")]
 impl Base91 {
-    pub fn encode(input: &[u8]) -> Result<String> {
-        let mut b = 0u32;
-        let mut n = 0u32;
-        let mut encoded = String::new();
+    pub fn encode(input: &[u8]) -> String {
+        let mut encoded = String::with_capacity(input.len() * 16 / 13 + 2);
+        let mut state = Base91State::default();
+        state.encode(input, |symbol| encoded.push(symbol as char));
+        state.encode_finish(|symbol| encoded.push(symbol as char));
+        encoded
+    }
+    pub fn decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = Vec::with_capacity(input.len() * 13 / 16 + 1);
+        let mut state = Base91State::default();
+        state.decode(input.as_bytes(), 0, |byte| decoded.push(byte))?;
+        state.decode_finish(|byte| decoded.push(byte));
+        Ok(decoded)
+    }
+}
+
+/// Bit queue of the Base91 codec, kept between calls so that the streaming
+/// adapters can feed it input in pieces.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+pub(crate) struct Base91State {
+    /// queued bits, least significant first
+    b: u32,
+    /// number of queued bits
+    n: u32,
+    /// first symbol of an incomplete pair while decoding
+    v: Option<u32>,
+}
 
+#[cfg(feature = "alloc")]
+impl Base91State {
+    pub(crate) fn encode(&mut self, input: &[u8], mut push: impl FnMut(u8)) {
         for byte in input {
-            b |= (*byte as u32) << n;
-            n += 8;
-            if n > 13 {
-                let mut v = b & 8191;
+            self.b |= (*byte as u32) << self.n;
+            self.n += 8;
+            if self.n > 13 {
+                let mut v = self.b & 8191;
                 if v > 88 {
-                    b >>= 13;
-                    n -= 13;
+                    self.b >>= 13;
+                    self.n -= 13;
                 } else {
-                    v = b & 16383;
-                    b >>= 14;
-                    n -= 14;
+                    v = self.b & 16383;
+                    self.b >>= 14;
+                    self.n -= 14;
                 }
-                let value = match BASE91_ALPHABET.chars().nth((v % 91) as usize) {
-                    Some(v) => v,
-                    None => {
-                        return Err(DecodeError::new(&format!(
-                            "get value from alphabet failed, index {}, max length {}",
-                            (v % 91) as usize,
-                            BASE91_ALPHABET.len(),
-                        ))
-                        .into())
-                    }
-                };
-                encoded.push(value);
-                let value = match BASE91_ALPHABET.chars().nth((v / 91) as usize) {
-                    Some(v) => v,
-                    None => {
-                        return Err(DecodeError::new(&format!(
-                            "get value from alphabet failed, index {}, max length {}",
-                            (v / 91) as usize,
-                            BASE91_ALPHABET.len(),
-                        ))
-                        .into())
-                    }
-                };
-                encoded.push(value);
+                push(BASE91_ALPHABET.symbol((v % 91) as usize));
+                push(BASE91_ALPHABET.symbol((v / 91) as usize));
             }
         }
-
-        if n > 0 {
-            let value = match BASE91_ALPHABET.chars().nth((b % 91) as usize) {
-                Some(v) => v,
-                None => {
-                    return Err(DecodeError::new(&format!(
-                        "get value from alphabet failed, index {}, max length {}",
-                        (b % 91) as usize,
-                        BASE91_ALPHABET.len(),
-                    ))
-                    .into())
-                }
-            };
-            encoded.push(value);
-            if n > 7 || b > 90 {
-                let value = match BASE91_ALPHABET.chars().nth((b / 91) as usize) {
-                    Some(v) => v,
-                    None => {
-                        return Err(DecodeError::new(&format!(
-                            "get value from alphabet failed, index {}, max length {}",
-                            (b / 91) as usize,
-                            BASE91_ALPHABET.len(),
-                        ))
-                        .into())
-                    }
-                };
-                encoded.push(value);
+    }
+    pub(crate) fn encode_finish(&mut self, mut push: impl FnMut(u8)) {
+        if self.n > 0 {
+            push(BASE91_ALPHABET.symbol((self.b % 91) as usize));
+            if self.n > 7 || self.b > 90 {
+                push(BASE91_ALPHABET.symbol((self.b / 91) as usize));
             }
         }
-
-        Ok(encoded)
+        *self = Base91State::default();
     }
-    pub fn decode(input: &str) -> Result<Vec<u8>> {
-        let mut decoded = Vec::new();
-        let mut b = 0u32;
-        let mut n = 0u32;
-        let mut v = -1;
-
-        for c in input.chars() {
-            let d = match BASE91_ALPHABET.find(c) {
-                Some(i) => i,
-                None => return Err(DecodeError::new("invalid base91 character").into()),
+    /// Decodes `input`, whose first byte sits at `offset` in the whole
+    /// encoded text.
+    pub(crate) fn decode(
+        &mut self,
+        input: &[u8],
+        offset: usize,
+        mut push: impl FnMut(u8),
+    ) -> Result<(), DecodeError> {
+        for (i, &byte) in input.iter().enumerate() {
+            let d = match BASE91_ALPHABET.value(byte) {
+                Some(d) => d as u32,
+                None => return Err(DecodeError::invalid_byte(input, i).offset_by(offset)),
             };
-            if v < 0 {
-                v = d as i32;
-            } else {
-                v += (d as i32) * 91;
-                b |= (v as u32) << n;
-                if (v & 8191) > 88 {
-                    n += 13;
-                } else {
-                    n += 14;
-                }
-                while n > 7 {
-                    decoded.push((b & 255) as u8);
-                    b >>= 8;
-                    n -= 8;
+            match self.v.take() {
+                None => self.v = Some(d),
+                Some(v) => {
+                    let v = v + d * 91;
+                    self.b |= v << self.n;
+                    if (v & 8191) > 88 {
+                        self.n += 13;
+                    } else {
+                        self.n += 14;
+                    }
+                    while self.n > 7 {
+                        push((self.b & 255) as u8);
+                        self.b >>= 8;
+                        self.n -= 8;
+                    }
                 }
-                v = -1;
             }
         }
-
-        if v + 1 > 0 {
-            decoded.push(((b | (v as u32) << n) & 255) as u8);
+        Ok(())
+    }
+    pub(crate) fn decode_finish(&mut self, mut push: impl FnMut(u8)) {
+        if let Some(v) = self.v {
+            push(((self.b | v << self.n) & 255) as u8);
         }
+        *self = Base91State::default();
+    }
+}
 
-        Ok(decoded)
+#[cfg(feature = "alloc")]
+impl Encoding for Base91 {
+    fn name(&self) -> &'static str {
+        "base91"
+    }
+    fn alphabet(&self) -> &'static str {
+        BASE91_ALPHABET.as_str()
+    }
+    fn is_padded(&self) -> bool {
+        false
+    }
+    fn encoded_len(&self, _input_len: usize) -> Option<usize> {
+        None
+    }
+    fn encode(&self, input: &[u8]) -> String {
+        Base91::encode(input)
+    }
+    fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        Base91::decode(input)
+    }
+}
+
+/// Appends the output of a fixed-block `encode_block` to `output`, going
+/// through a stack buffer so that only the new symbols are checked as UTF-8.
+#[cfg(feature = "alloc")]
+fn append_blocks(
+    input: &[u8],
+    output: &mut String,
+    encoded_len: impl Fn(usize) -> usize,
+    encode_block: impl Fn(&[u8], &mut [u8]),
+) {
+    // 960 bytes is a whole number of blocks for Base16, Base32, Base64 and
+    // Base85, so only the last chunk can end with a partial block
+    let mut buffer = [0u8; 1920];
+    output.reserve(encoded_len(input.len()));
+    for chunk in input.chunks(960) {
+        let len = encoded_len(chunk.len());
+        encode_block(chunk, &mut buffer[..len]);
+        output.push_str(core::str::from_utf8(&buffer[..len]).expect("encoders only produce ASCII"));
+    }
+}
+
+/// Spreads the `len` symbols at the front of `buffer` over lines of `width`
+/// joined by `separator`, filling `buffer` exactly. Lines are moved starting
+/// from the last one, so none is overwritten before it has been moved.
+fn wrap_lines(buffer: &mut [u8], len: usize, width: usize, separator: &[u8]) {
+    if len == 0 {
+        return;
+    }
+    let mut end = buffer.len();
+    let mut line = (len - 1) / width * width;
+    loop {
+        let line_len = (len - line).min(width);
+        end -= line_len;
+        buffer.copy_within(line..line + line_len, end);
+        if line == 0 {
+            break;
+        }
+        end -= separator.len();
+        buffer[end..end + separator.len()].copy_from_slice(separator);
+        line -= width;
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     #[test]
     fn base16() -> Result<()> {
         let data = b"Hello";
-        let enc = Base16::encode(data)?;
+        let enc = Base16::encode(data);
         println!("enc: {}", enc);
         let dec = Base16::decode(&enc)?;
         assert_eq!(dec, data);
+        assert_eq!(Base16::decode(&enc.to_uppercase())?, data);
         Ok(())
     }
     #[test]
     fn base32() -> Result<()> {
         let data = b"Hello";
-        let enc = Base32::encode(data)?;
+        let enc = Base32::encode(data);
         println!("enc: {}", enc);
         let dec = Base32::decode(&enc)?;
         assert_eq!(dec, data);
         Ok(())
     }
     #[test]
+    fn base32_engines() -> Result<()> {
+        // RFC 4648 §10 test vectors
+        let vectors = [
+            ("", "", ""),
+            ("f", "MY======", "CO======"),
+            ("fo", "MZXQ====", "CPNG===="),
+            ("foo", "MZXW6===", "CPNMU==="),
+            ("foob", "MZXW6YQ=", "CPNMUOG="),
+            ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+            ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+        ];
+        for (data, base32, base32hex) in vectors {
+            assert_eq!(Base32Engine::STANDARD.encode(data.as_bytes()), base32);
+            assert_eq!(Base32Engine::HEX.encode(data.as_bytes()), base32hex);
+            assert_eq!(Base32Engine::STANDARD.decode(base32)?, data.as_bytes());
+            assert_eq!(Base32Engine::HEX.decode(base32hex)?, data.as_bytes());
+
+            let unpadded = base32hex.trim_end_matches('=');
+            assert_eq!(Base32Engine::HEX_NO_PAD.encode(data.as_bytes()), unpadded);
+            assert_eq!(Base32Engine::HEX_NO_PAD.decode(unpadded)?, data.as_bytes());
+        }
+
+        assert_eq!(
+            Base32Engine::STANDARD.decode("MZXW6"),
+            Err(DecodeError::InvalidPadding { index: 5 })
+        );
+        assert_eq!(
+            Base32Engine::STANDARD_NO_PAD.decode("MZXW6==="),
+            Err(DecodeError::InvalidPadding { index: 5 })
+        );
+        assert_eq!(
+            Base32Engine::HEX.decode("cpnmu==="),
+            Err(DecodeError::InvalidCharacter {
+                index: 0,
+                character: 'c'
+            })
+        );
+        assert_eq!(
+            Base32Engine::HEX
+                .with_ignore_case(true)
+                .decode("cpnmu===")?,
+            b"foo"
+        );
+        Ok(())
+    }
+    #[test]
+    fn base32_strict() {
+        let cases = [
+            ("MY=", DecodeError::InvalidPadding { index: 2 }),
+            ("MY======MZXW6===", DecodeError::InvalidPadding { index: 8 }),
+            ("MY=A====", DecodeError::InvalidPadding { index: 3 }),
+            (
+                "MY=a====",
+                DecodeError::InvalidCharacter {
+                    index: 3,
+                    character: 'a',
+                },
+            ),
+            ("M=======", DecodeError::InvalidLength { length: 8 }),
+            ("MZX=====", DecodeError::InvalidLength { length: 8 }),
+            ("MZXW6Y==", DecodeError::InvalidLength { length: 8 }),
+            ("MZ======", DecodeError::InvalidTrailingBits { index: 1 }),
+        ];
+        for (input, error) in cases {
+            assert_eq!(Base32::decode(input), Err(error), "{}", input);
+        }
+        assert_eq!(
+            Base32Engine::STANDARD_NO_PAD.decode("M"),
+            Err(DecodeError::InvalidLength { length: 1 })
+        );
+        assert_eq!(
+            Base32Engine::STANDARD_NO_PAD.decode("MZ"),
+            Err(DecodeError::InvalidTrailingBits { index: 1 })
+        );
+    }
+    #[test]
+    fn crockford() -> Result<()> {
+        let data = b"Hello, world";
+        let enc = Crockford::encode(data);
+        assert_eq!(enc, "91JPRV3F5GG7EVVJDHJ0");
+        assert_eq!(Crockford::decode(&enc)?, data);
+        assert_eq!(Crockford::decode("91jp-rv3f-5gg7-evvj-dhjo")?, data);
+        assert_eq!(Crockford::encode_with_check(data), "91JPRV3F5GG7EVVJDHJ08");
+        assert_eq!(Crockford::decode_with_check("91JPRV3F5GG7EVVJDHJ08")?, data);
+        assert_eq!(
+            Crockford::decode_with_check("91JPRV3F5GG7EVVJDHJ09"),
+            Err(DecodeError::ChecksumMismatch {
+                expected: 8,
+                found: 9
+            })
+        );
+        assert_eq!(
+            Crockford::decode("91JU"),
+            Err(DecodeError::InvalidCharacter {
+                index: 3,
+                character: 'U'
+            })
+        );
+        assert_eq!(
+            Crockford::decode("1"),
+            Err(DecodeError::InvalidLength { length: 1 })
+        );
+        assert_eq!(
+            Crockford::decode_with_check("91JPRV3F5GG7EVVJDHJ8"),
+            Err(DecodeError::InvalidLength { length: 19 })
+        );
+        assert_eq!(
+            Crockford::decode("ZZ"),
+            Err(DecodeError::InvalidTrailingBits { index: 1 })
+        );
+        assert_eq!(
+            Crockford::decode("91jp-rv3f-5gg7-evvj-dhjz"),
+            Err(DecodeError::InvalidTrailingBits { index: 23 })
+        );
+
+        assert_eq!(Crockford::encode_u64(0), "0");
+        assert_eq!(Crockford::encode_u64(u64::MAX), "FZZZZZZZZZZZZ");
+        assert_eq!(Crockford::encode_u64_with_check(u64::MAX), "FZZZZZZZZZZZZB");
+        assert_eq!(
+            Crockford::encode_u128_with_check(u128::MAX),
+            "7ZZZZZZZZZZZZZZZZZZZZZZZZZ*"
+        );
+        assert_eq!(Crockford::decode_u64("fzzz-zzzz-zzzz-z")?, u64::MAX);
+        assert_eq!(
+            Crockford::decode_u128_with_check("7ZZZZZZZZZZZZZZZZZZZZZZZZZ*")?,
+            u128::MAX
+        );
+        assert_eq!(Crockford::decode_u64("iLoO")?, 0b00001_00001_00000_00000);
+        assert_eq!(Crockford::decode_u64_with_check("14u")?, 36);
+        assert_eq!(
+            Crockford::decode_u64("G000000000000"),
+            Err(DecodeError::Overflow { index: 12 })
+        );
+        assert_eq!(
+            Crockford::decode_u64("--"),
+            Err(DecodeError::InvalidLength { length: 2 })
+        );
+        assert_eq!(
+            Crockford::decode_u64_with_check("16J\u{e9}"),
+            Err(DecodeError::InvalidCharacter {
+                index: 3,
+                character: '\u{e9}'
+            })
+        );
+        Ok(())
+    }
+    #[test]
+    fn zbase32() -> Result<()> {
+        // test vectors of the z-base-32 specification
+        let vectors: [(&[u8], usize, &str); 10] = [
+            (&[0x00], 1, "y"),
+            (&[0x80], 1, "o"),
+            (&[0x40], 2, "e"),
+            (&[0xc0], 2, "a"),
+            (&[0x00, 0x00], 10, "yy"),
+            (&[0x80, 0x80], 10, "on"),
+            (&[0x8b, 0x88, 0x80], 20, "tqre"),
+            (&[0xf0, 0xbf, 0xc7], 24, "6n9hq"),
+            (&[0xd4, 0x7a, 0x04], 24, "4t7ye"),
+            (&[0xf5, 0x57, 0xbb, 0x0c], 30, "6im5sd"),
+        ];
+        for (data, bits, enc) in vectors {
+            assert_eq!(ZBase32::encode_bits(data, bits), enc);
+            assert_eq!(ZBase32::decode_bits(enc, bits)?, data);
+        }
+
+        let data = b"Hello, world";
+        let enc = ZBase32::encode(data);
+        assert_eq!(ZBase32::decode(&enc)?, data);
+        assert_eq!(ZBase32::decode(&enc.to_uppercase())?, data);
+        // bits past the bit length are ignored
+        assert_eq!(ZBase32::decode_bits("9", 1)?, [0x80]);
+        assert_eq!(
+            ZBase32::decode_bits("tqre", 15),
+            Err(DecodeError::InvalidLength { length: 4 })
+        );
+        assert_eq!(
+            ZBase32::decode("yyv"),
+            Err(DecodeError::InvalidLength { length: 3 })
+        );
+        assert_eq!(
+            ZBase32::decode("yv"),
+            Err(DecodeError::InvalidCharacter {
+                index: 1,
+                character: 'v'
+            })
+        );
+        Ok(())
+    }
+    #[test]
     fn base36() -> Result<()> {
         let data = b"Hello";
-        let enc = Base36::encode(data)?;
+        let enc = Base36::encode(data);
         println!("enc: {}", enc);
         let dec = Base36::decode(&enc)?;
         assert_eq!(dec, data);
         Ok(())
     }
     #[test]
+    fn base36_leading_zeros() -> Result<()> {
+        assert_eq!(Base36::encode(&[]), "");
+        assert_eq!(Base36::encode(&[0]), "0");
+        assert_eq!(Base36::encode(&[0, 0, 35]), "00z");
+        assert_eq!(Base36Upper::encode(&[0, 0, 35]), "00Z");
+        assert_eq!(Base36::decode("0")?, [0]);
+        assert_eq!(Base36::decode("")?, []);
+        assert!(Base36::decode("Z").is_err());
+
+        let mut data = vec![0, 0];
+        data.extend_from_slice(&[0xa5; 40]);
+        let enc = Base36Upper::encode(&data);
+        assert_eq!(Base36Upper::decode(&enc)?, data);
+        assert_eq!(Base36::decode(&enc.to_lowercase())?, data);
+        Ok(())
+    }
+    #[test]
     fn base58() -> Result<()> {
         let data = b"Hello";
-        let enc = Base58::encode(data)?;
+        let enc = Base58::encode(data);
         println!("enc: {}", enc);
         let dec = Base58::decode(&enc)?;
         assert_eq!(dec, data);
         Ok(())
     }
     #[test]
+    fn base58_bitcoin_vectors() -> Result<()> {
+        // from src/test/data/base58_encode_decode.json in Bitcoin Core
+        let vectors = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            (
+                "73696d706c792061206c6f6e6720737472696e67",
+                "2cFupjhnEsSn59qHXstmK2ffpLv2",
+            ),
+            (
+                "00eb15231dfceb60925886b67d065299925915aeb172c06647",
+                "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+            ),
+            ("516b6fcd0f", "ABnLTmg"),
+            ("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+            ("572e4794", "3EFU7m"),
+            ("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+            ("10c8511e", "Rt5zm"),
+            ("00000000000000000000", "1111111111"),
+            (
+                "000111d38e5fc9071ffcd20b4a763cc9ae4f252bb4e48fd66a835e252ada93ff480d6dd43dc62a641155a5",
+                "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            ),
+        ];
+        for (hex, b58) in vectors {
+            let data = Base16::decode(hex)?;
+            assert_eq!(Base58::encode(&data), b58);
+            assert_eq!(Base58::decode(b58)?, data);
+        }
+        assert!(Base58::decode("0OIl").is_err());
+        Ok(())
+    }
+    #[test]
+    fn base58_alphabets() -> Result<()> {
+        let data = b"\0Hello World";
+        for (engine, encoded, name) in [
+            (Base58Engine::BITCOIN, "1JxF12TrwUP45BMd", "base58"),
+            (Base58Engine::RIPPLE, "rJxErpTiA7PhnBMd", "base58ripple"),
+            (Base58Engine::FLICKR, "1iXf12sRWto45bmC", "base58flickr"),
+        ] {
+            assert_eq!(engine.encode(data), encoded);
+            assert_eq!(engine.decode(encoded)?, data);
+            assert_eq!(Encoding::name(&engine), name);
+        }
+        assert_eq!(
+            Base58Engine::FLICKR.encode(&4379041379u64.to_be_bytes()[3..]),
+            "7EXJrr"
+        );
+        assert!(Base58Engine::RIPPLE.decode("r0").is_err());
+
+        static SORTED: Alphabet<58> =
+            Alphabet::new("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+        let engine = Base58Engine::new(&SORTED);
+        assert_eq!(Encoding::name(&engine), "base58");
+        assert_eq!(
+            Encoding::name(&engine.with_name("base58sorted")),
+            "base58sorted"
+        );
+
+        let account = Base16::decode("b5f762798a53d543a014caf8b297cff8f2f937e8")?;
+        let address = Base58Engine::RIPPLE.encode_check(&[0x00], &account);
+        assert_eq!(address, "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+        assert_eq!(
+            Base58Engine::RIPPLE.decode_check(&address, 1)?,
+            (vec![0x00], account)
+        );
+        Ok(())
+    }
+    #[test]
+    fn base58check() -> Result<()> {
+        let hash = Base16::decode("010966776006953d5567439e5e39f86a0d273bee")?;
+        let address = Base58Check::encode(0x00, &hash);
+        assert_eq!(address, "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+        assert_eq!(Base58Check::decode(&address)?, (0x00, hash));
+
+        let key =
+            Base16::decode("0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d")?;
+        let wif = "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ";
+        assert_eq!(Base58Check::encode(0x80, &key), wif);
+        assert_eq!(Base58Check::decode(wif)?, (0x80, key.clone()));
+
+        let prefix = [0x04, 0x88, 0xb2, 0x1e];
+        let encoded = Base58Check::encode_with_prefix(&prefix, &key);
+        assert_eq!(
+            Base58Check::decode_with_prefix(&encoded, 4)?,
+            (prefix.to_vec(), key)
+        );
+
+        assert_eq!(
+            Base58Check::decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"),
+            Err(DecodeError::ChecksumMismatch {
+                expected: 0xd61967f6,
+                found: 0xd61967f7
+            })
+        );
+        assert_eq!(
+            Base58Check::decode("3QJmnh"),
+            Err(DecodeError::InvalidLength { length: 6 })
+        );
+        assert_eq!(
+            Base58Check::decode("111"),
+            Err(DecodeError::InvalidLength { length: 3 })
+        );
+        assert_eq!(Encoding::decode(&Base58Check {}, "3QJmnh")?, []);
+        Ok(())
+    }
+    #[test]
+    fn monero_base58() -> Result<()> {
+        let vectors = [
+            ("00", "11"),
+            ("39", "1z"),
+            ("ff", "5Q"),
+            ("0000", "111"),
+            ("0039", "11z"),
+            ("0100", "15R"),
+            ("ffff", "LUv"),
+            ("ffffffffffffffff", "jpXCZedGfVQ"),
+            ("0000000000000000", "11111111111"),
+            ("0102030405060708090a", "1An6UebxCZd1gu"),
+            ("06156013762879f7ffffffffff", "22222222222VtB5VXc"),
+        ];
+        for (hex, b58) in vectors {
+            let data = Base16::decode(hex)?;
+            assert_eq!(MoneroBase58::encode(&data), b58);
+            assert_eq!(MoneroBase58::encoded_len(data.len()), b58.len());
+            assert_eq!(MoneroBase58::decode(b58)?, data);
+        }
+
+        assert_eq!(
+            MoneroBase58::decode("5R"),
+            Err(DecodeError::Overflow { index: 0 })
+        );
+        assert_eq!(
+            MoneroBase58::decode("11111111111LUw"),
+            Err(DecodeError::Overflow { index: 11 })
+        );
+        assert_eq!(
+            MoneroBase58::decode("jpXCZedGfVR"),
+            Err(DecodeError::Overflow { index: 0 })
+        );
+        assert_eq!(
+            MoneroBase58::decode("zzzzzzzzzzz"),
+            Err(DecodeError::Overflow { index: 0 })
+        );
+        assert_eq!(
+            MoneroBase58::decode("1111"),
+            Err(DecodeError::InvalidLength { length: 4 })
+        );
+        assert_eq!(
+            MoneroBase58::decode("1O"),
+            Err(DecodeError::InvalidCharacter {
+                index: 1,
+                character: 'O'
+            })
+        );
+        Ok(())
+    }
+    #[test]
     fn base62() -> Result<()> {
         let data = b"Hello";
-        let enc = Base62::encode(data)?;
+        let enc = Base62::encode(data);
         println!("enc: {}", enc);
         let dec = Base62::decode(&enc)?;
         assert_eq!(dec, data);
         Ok(())
     }
     #[test]
+    fn base62_lengths() -> Result<()> {
+        let mut data = Vec::new();
+        for len in 0..=256usize {
+            let enc = Base62::encode(&data);
+            assert_eq!(Base62::decode(&enc)?, data, "length {}", len);
+            // mix zero bytes in, starting with a leading one
+            data.push(if len % 3 == 0 { 0 } else { (len * 37) as u8 });
+        }
+        assert_eq!(Base62::encode(&[0, 0, 1]), "001");
+        assert_eq!(Base62::encode(&[0xff; 32]).len(), 43);
+        Ok(())
+    }
+    #[test]
     fn base64() -> Result<()> {
         let data = b"Hello";
-        let enc = Base64::encode(data)?;
+        let enc = Base64::encode(data);
         println!("enc: {}", enc);
         let dec = Base64::decode(&enc)?;
         assert_eq!(dec, data);
         Ok(())
     }
     #[test]
+    fn base64_engines() -> Result<()> {
+        let data = [0xfb, 0xff, 0xbf, 0x3e];
+        assert_eq!(Base64Engine::STANDARD.encode(&data), "+/+/Pg==");
+        assert_eq!(Base64Engine::STANDARD_NO_PAD.encode(&data), "+/+/Pg");
+        assert_eq!(Base64Engine::URL_SAFE.encode(&data), "-_-_Pg==");
+        assert_eq!(Base64Engine::URL_SAFE_NO_PAD.encode(&data), "-_-_Pg");
+
+        // JWT header segment
+        let header = br#"{"alg":"HS256","typ":"JWT"}"#;
+        let enc = Base64Engine::URL_SAFE_NO_PAD.encode(header);
+        assert_eq!(enc, "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9");
+        assert_eq!(Base64Engine::URL_SAFE_NO_PAD.decode(&enc)?, header);
+
+        for len in 0..=16 {
+            let data: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(97)).collect();
+            for engine in [
+                Base64Engine::STANDARD,
+                Base64Engine::STANDARD_NO_PAD,
+                Base64Engine::URL_SAFE,
+                Base64Engine::URL_SAFE_NO_PAD,
+            ] {
+                let enc = engine.encode(&data);
+                assert_eq!(enc.len(), engine.encoded_len(len));
+                assert_eq!(engine.decode(&enc)?, data);
+            }
+        }
+        Ok(())
+    }
+    #[test]
+    fn base64_padding_modes() {
+        let optional = Base64Engine::URL_SAFE.with_decode_padding(DecodePadding::Optional);
+        assert_eq!(
+            optional.decode("-_-_Pg==").unwrap(),
+            [0xfb, 0xff, 0xbf, 0x3e]
+        );
+        assert_eq!(optional.decode("-_-_Pg").unwrap(), [0xfb, 0xff, 0xbf, 0x3e]);
+
+        assert_eq!(
+            Base64Engine::URL_SAFE.decode("-_-_Pg"),
+            Err(DecodeError::InvalidPadding { index: 6 })
+        );
+        assert_eq!(
+            Base64Engine::URL_SAFE_NO_PAD.decode("-_-_Pg=="),
+            Err(DecodeError::InvalidPadding { index: 6 })
+        );
+        assert_eq!(
+            Base64::decode("-_-_Pg=="),
+            Err(DecodeError::InvalidCharacter {
+                index: 0,
+                character: '-'
+            })
+        );
+    }
+    #[test]
+    fn base64_strict() {
+        let strict = Base64Engine::STANDARD;
+        let lenient = Base64Engine::STANDARD.with_decode_mode(DecodeMode::Lenient);
+        assert_eq!(
+            strict.decode("SGk=SGk="),
+            Err(DecodeError::InvalidPadding { index: 4 })
+        );
+        assert_eq!(lenient.decode("SGk=SGk=").unwrap(), b"Hi");
+        assert_eq!(
+            strict.decode("SGk=="),
+            Err(DecodeError::InvalidPadding { index: 3 })
+        );
+        assert_eq!(
+            strict.decode("S==="),
+            Err(DecodeError::InvalidLength { length: 4 })
+        );
+        assert_eq!(lenient.decode("S===").unwrap(), b"");
+        assert_eq!(
+            strict.decode("SGl="),
+            Err(DecodeError::InvalidTrailingBits { index: 2 })
+        );
+        assert_eq!(lenient.decode("SGl=").unwrap(), b"Hi");
+        assert_eq!(
+            Base64Engine::STANDARD_NO_PAD.decode("SGl"),
+            Err(DecodeError::InvalidTrailingBits { index: 2 })
+        );
+        assert_eq!(
+            strict.decode("SGk"),
+            Err(DecodeError::InvalidPadding { index: 3 })
+        );
+        assert_eq!(strict.decode("").unwrap(), b"");
+    }
+    #[test]
+    fn base64_line_wrap() -> Result<()> {
+        let data: Vec<u8> = (0..=255).collect();
+        let single = Base64::encode(&data);
+
+        let mime = Base64Engine::MIME.encode(&data);
+        let lines: Vec<&str> = mime.split("\r\n").collect();
+        assert_eq!(lines.concat(), single);
+        assert!(lines[..lines.len() - 1].iter().all(|line| line.len() == 76));
+        assert_eq!(mime.len(), Base64Engine::MIME.encoded_len(data.len()));
+        assert_eq!(Base64Engine::MIME.decode(&mime)?, data);
+
+        let pem = Base64Engine::PEM.encode(&data);
+        assert!(pem.split('\n').all(|line| line.len() <= 64));
+        assert!(!pem.ends_with('\n'));
+        let mut buffer = [0u8; 512];
+        let len = Base64Engine::PEM.encode_to_slice(&data, &mut buffer)?;
+        assert_eq!(&buffer[..len], pem.as_bytes());
+        assert_eq!(Base64Engine::PEM.decode(&pem)?, data);
+        assert_eq!(Base64Engine::PEM.encode(b""), "");
+        assert_eq!(Base64Engine::PEM.encode(&data[..48]).len(), 64);
+        Ok(())
+    }
+    #[test]
+    fn base64_decode_ignore() {
+        let wrapped = "SGVs\r\nbG8s IHdv\tcmxk\n";
+        assert_eq!(
+            Base64::decode(wrapped),
+            Err(DecodeError::InvalidCharacter {
+                index: 4,
+                character: '\r'
+            })
+        );
+        let whitespace = Base64Engine::STANDARD.with_decode_ignore(DecodeIgnore::Whitespace);
+        assert_eq!(whitespace.decode(wrapped).unwrap(), b"Hello, world");
+        assert_eq!(whitespace.decode("SGk=\n").unwrap(), b"Hi");
+        assert_eq!(
+            whitespace.decode("SG*k="),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: '*'
+            })
+        );
+        assert_eq!(
+            whitespace.decode("SGk=\nSGk="),
+            Err(DecodeError::InvalidPadding { index: 5 })
+        );
+
+        let garbage = Base64Engine::STANDARD.with_decode_ignore(DecodeIgnore::Garbage);
+        assert_eq!(garbage.decode("S*G-k.=").unwrap(), b"Hi");
+        assert_eq!(Base64Engine::MIME.decode("SG\u{e9}k=\r\n").unwrap(), b"Hi");
+    }
+    #[test]
     fn base85() -> Result<()> {
         let data = b"Hello";
-        let enc = Base85::encode(data)?;
+        let enc = Base85::encode(data);
         println!("enc: {}", enc);
         let dec = Base85::decode(&enc)?;
         assert_eq!(dec, data);
@@ -666,10 +2949,245 @@ mod tests {
     #[test]
     fn base91() -> Result<()> {
         let data = b"Hello";
-        let enc = Base91::encode(data)?;
+        let enc = Base91::encode(data);
         println!("enc: {}", enc);
         let dec = Base91::decode(&enc)?;
         assert_eq!(dec, data);
         Ok(())
     }
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            Base16::decode("48g5"),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: 'g'
+            })
+        );
+        assert_eq!(
+            Base16::decode("48é5"),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: 'é'
+            })
+        );
+        assert_eq!(
+            Base16::decode("485"),
+            Err(DecodeError::InvalidLength { length: 3 })
+        );
+        assert_eq!(
+            Base58::decode("11O"),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: 'O'
+            })
+        );
+        assert_eq!(
+            Base64::decode("SGV sbG8"),
+            Err(DecodeError::InvalidCharacter {
+                index: 3,
+                character: ' '
+            })
+        );
+        assert_eq!(
+            Base85::decode("12345~~~~~"),
+            Err(DecodeError::Overflow { index: 9 })
+        );
+        assert_eq!(
+            Base85::decode("123456"),
+            Err(DecodeError::InvalidLength { length: 6 })
+        );
+    }
+    #[test]
+    fn slice_api() -> Result<()> {
+        let data = b"Hello, world";
+        let mut encoded = [0u8; 32];
+        let mut decoded = [0u8; 16];
+
+        let len = Base16::encode_to_slice(data, &mut encoded)?;
+        assert_eq!(len, Base16::encoded_len(data.len()));
+        let text = core::str::from_utf8(&encoded[..len]).unwrap();
+        assert_eq!(text, Base16::encode(data));
+        let n = Base16::decode_to_slice(text, &mut decoded)?;
+        assert_eq!(&decoded[..n], data);
+
+        let len = Base32::encode_to_slice(data, &mut encoded)?;
+        let text = core::str::from_utf8(&encoded[..len]).unwrap();
+        assert_eq!(text, Base32::encode(data));
+        let n = Base32::decode_to_slice(text, &mut decoded)?;
+        assert_eq!(&decoded[..n], data);
+
+        let len = Base64::encode_to_slice(data, &mut encoded)?;
+        let text = core::str::from_utf8(&encoded[..len]).unwrap();
+        assert_eq!(text, Base64::encode(data));
+        let n = Base64::decode_to_slice(text, &mut decoded)?;
+        assert_eq!(&decoded[..n], data);
+
+        assert_eq!(
+            Base64::decode_to_slice(text, &mut decoded[..11]),
+            Err(DecodeError::BufferTooSmall { needed: 12 })
+        );
+        assert_eq!(
+            Base64::encode_to_slice(data, &mut encoded[..15]),
+            Err(EncodeError::BufferTooSmall { needed: 16 })
+        );
+        Ok(())
+    }
+    #[test]
+    fn encode_append() -> Result<()> {
+        let data: Vec<u8> = (0..3000u32).map(|i| (i * 7 + i / 256) as u8).collect();
+        for len in [0, 1, 2, 3, 4, 5, 959, 960, 961, 1919, 3000] {
+            let input = &data[..len];
+            let mut out = String::from("prefix:");
+
+            Base16::encode_append(input, &mut out);
+            assert_eq!(out.len(), 7 + Base16::encoded_len(len));
+            assert_eq!(Base16::decode(&out[7..])?, input);
+            assert!(Base16::decoded_len_estimate(out.len() - 7) >= len);
+
+            out.truncate(7);
+            Base32::encode_append(input, &mut out);
+            assert_eq!(out.len(), 7 + Base32::encoded_len(len));
+            assert_eq!(Base32::decode(&out[7..])?, input);
+            assert!(Base32::decoded_len_estimate(out.len() - 7) >= len);
+
+            out.truncate(7);
+            Base64::encode_append(input, &mut out);
+            assert_eq!(out.len(), 7 + Base64::encoded_len(len));
+            assert_eq!(Base64::decode(&out[7..])?, input);
+            assert!(Base64::decoded_len_estimate(out.len() - 7) >= len);
+
+            out.truncate(7);
+            Base85::encode_append(input, &mut out);
+            assert_eq!(out.len(), 7 + Base85::encoded_len(len));
+            assert_eq!(Base85::decode(&out[7..])?, input);
+            assert_eq!(Base85::decoded_len_estimate(out.len() - 7), len);
+        }
+        Ok(())
+    }
+    #[test]
+    fn base85_slice() -> Result<()> {
+        let data = b"Hello, world!";
+        let mut encoded = [0u8; 17];
+        let len = Base85::encode_to_slice(data, &mut encoded)?;
+        assert_eq!(len, 17);
+        let text = core::str::from_utf8(&encoded).unwrap();
+        let mut decoded = [0u8; 13];
+        assert_eq!(Base85::decode_to_slice(text, &mut decoded)?, 13);
+        assert_eq!(&decoded, data);
+        assert_eq!(
+            Base85::encode_to_slice(data, &mut [0u8; 16]),
+            Err(EncodeError::BufferTooSmall { needed: 17 })
+        );
+        Ok(())
+    }
+    #[test]
+    fn encoding_trait() -> Result<()> {
+        let codecs: [&dyn Encoding; 15] = [
+            &Base16 {},
+            &Base32 {},
+            &Crockford {},
+            &ZBase32 {},
+            &Base36 {},
+            &Base36Upper {},
+            &Base58 {},
+            &Base58Engine::RIPPLE,
+            &Base58Check {},
+            &MoneroBase58 {},
+            &Base62 {},
+            &Base64 {},
+            &Base85 {},
+            &Ascii85Engine::BTOA,
+            &Base91 {},
+        ];
+        let data = b"Hello, world";
+        for codec in codecs {
+            let enc = codec.encode(data);
+            if let Some(len) = codec.encoded_len(data.len()) {
+                assert_eq!(enc.len(), len, "{}", codec.name());
+            }
+            assert!(enc
+                .chars()
+                .all(|c| c == '=' || codec.alphabet().contains(c)));
+            assert_eq!(codec.decode(&enc)?, data, "{}", codec.name());
+        }
+        Ok(())
+    }
+    #[test]
+    fn ascii85() -> Result<()> {
+        let vectors: [(&[u8], &str, &str); 7] = [
+            (b"Hello, world", "<~87cURD_*#TDfTZ)~>", "87cURD_*#TDfTZ)"),
+            (b"", "<~~>", ""),
+            (b"\0\0\0\0", "<~z~>", "z"),
+            (b"\0\0\0", "<~!!!!~>", "!!!!"),
+            (b"    ", "<~+<VdL~>", "y"),
+            (b"\xff\xff\xff\xff", "<~s8W-!~>", "s8W-!"),
+            (b"\0\0\0\0abc    de", "<~z@:E^h+<Ve;AH~>", "z@:E^h+<Ve;AH"),
+        ];
+        for (data, adobe, btoa) in vectors {
+            assert_eq!(Ascii85::encode(data), adobe);
+            assert_eq!(Ascii85::decode(adobe)?, data);
+            assert_eq!(Ascii85Engine::BTOA.encode(data), btoa);
+            assert_eq!(Ascii85Engine::BTOA.decode(btoa)?, data);
+        }
+
+        // PDF streams leave out the opening delimiter and wrap lines
+        assert_eq!(
+            Ascii85::decode("  87cUR D_*#T\r\nDfTZ)~>\n")?,
+            b"Hello, world"
+        );
+        assert_eq!(Ascii85::decode("<~ z\nz ~>")?, [0; 8]);
+
+        assert_eq!(Encoding::name(&Ascii85Engine::ADOBE), "ascii85");
+        assert_eq!(Encoding::name(&Ascii85Engine::BTOA), "btoa");
+        Ok(())
+    }
+    #[test]
+    fn ascii85_errors() {
+        assert_eq!(
+            Ascii85::decode("<~87cUR"),
+            Err(DecodeError::InvalidLength { length: 7 })
+        );
+        assert_eq!(
+            Ascii85::decode("<~87cUR~> x"),
+            Err(DecodeError::InvalidCharacter {
+                index: 10,
+                character: 'x'
+            })
+        );
+        // `z` only stands for a whole group
+        assert_eq!(
+            Ascii85::decode("<~87z~>"),
+            Err(DecodeError::InvalidCharacter {
+                index: 4,
+                character: 'z'
+            })
+        );
+        // `y` needs the btoa shortcut
+        assert_eq!(
+            Ascii85::decode("<~y~>"),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: 'y'
+            })
+        );
+        assert_eq!(
+            Ascii85::decode("<~s8W-\"~>"),
+            Err(DecodeError::Overflow { index: 6 })
+        );
+        assert_eq!(
+            Ascii85Engine::BTOA.decode("!"),
+            Err(DecodeError::InvalidLength { length: 1 })
+        );
+    }
+    #[test]
+    fn base85_lengths() -> Result<()> {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in 0..16 {
+            let enc = Base85::encode(&data[..len]);
+            assert_eq!(Some(enc.len()), Base85 {}.encoded_len(len));
+            assert_eq!(Base85::decode(&enc)?, &data[..len]);
+        }
+        Ok(())
+    }
 }